@@ -0,0 +1,265 @@
+//! This module specifies the [`CombatAi`] trait, used to decide what
+//! computer-controlled combatants do on their turn, along with a few built-in
+//! strategies.
+
+use crate::battle::{Battle, BattleAction, BattleState, CombatantId, Side};
+use crate::inventory::ItemEffect;
+use crate::service::{RngService, SeededRng};
+
+/// A strategy for choosing a combatant's action on their turn.
+pub trait CombatAi {
+    /// Chooses the action the given combatant should take.
+    ///
+    /// The chosen action should be valid for the current state of the
+    /// battle. If the battle has already ended, any action may be returned.
+    fn choose_action(&mut self, battle: &Battle, actor: CombatantId) -> BattleAction;
+}
+
+/// Returns the target the combatant should attack: the opponent with the
+/// least health left.
+fn weakest_target(battle: &Battle, actor: CombatantId) -> Option<CombatantId> {
+    battle.valid_targets(actor).into_iter()
+        .min_by_key(|&target| battle.combatant(target).map(|target| target.health.current()))
+}
+
+/// A strategy that always attacks, focusing on the opponent with the least
+/// health left to take them out of the fight as soon as possible.
+///
+/// # Examples
+///
+/// ```
+/// use druid_game::ai::{AggressiveAi, CombatAi};
+/// use druid_game::battle::{Battle, BattleAction, CombatantId, Side};
+/// use druid_game::combat::Party;
+/// use druid_game::combatant::Combatant;
+///
+/// let allies = Party::new(vec![
+///     Combatant::new("Alice".to_string()),
+///     Combatant::new("Bob".to_string()),
+/// ]);
+/// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+/// let mut battle = Battle::new(allies, enemies);
+/// battle.allies.members[1].health.damage(4);
+///
+/// let vim = CombatantId { side: Side::Enemies, index: 0 };
+/// let bob = CombatantId { side: Side::Allies, index: 1 };
+/// assert_eq!(BattleAction::Attack { target: bob }, AggressiveAi.choose_action(&battle, vim));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AggressiveAi;
+
+impl CombatAi for AggressiveAi {
+    fn choose_action(&mut self, battle: &Battle, actor: CombatantId) -> BattleAction {
+        match weakest_target(battle, actor) {
+            Some(target) => BattleAction::Attack { target },
+            None => BattleAction::Defend,
+        }
+    }
+}
+
+/// A strategy that looks after its own side first. If an ally, or the
+/// combatant themself, is at or below half health, the most injured of them
+/// is healed, with an item if the combatant has a healing item and with a
+/// [`BattleAction::Heal`] otherwise. When nobody needs healing, it attacks
+/// like [`AggressiveAi`].
+///
+/// # Examples
+///
+/// ```
+/// use druid_game::ai::{CombatAi, DefensiveAi};
+/// use druid_game::battle::{Battle, BattleAction, CombatantId, Side};
+/// use druid_game::combat::Party;
+/// use druid_game::combatant::Combatant;
+///
+/// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+/// let enemies = Party::new(vec![
+///     Combatant::new("Vim".to_string()),
+///     Combatant::new("Nano".to_string()),
+/// ]);
+/// let mut battle = Battle::new(allies, enemies);
+/// battle.enemies.members[1].health.damage(6);
+///
+/// let vim = CombatantId { side: Side::Enemies, index: 0 };
+/// let nano = CombatantId { side: Side::Enemies, index: 1 };
+/// assert_eq!(BattleAction::Heal { target: nano }, DefensiveAi.choose_action(&battle, vim));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefensiveAi;
+
+impl CombatAi for DefensiveAi {
+    fn choose_action(&mut self, battle: &Battle, actor: CombatantId) -> BattleAction {
+        let most_injured = battle.valid_support_targets(actor).into_iter()
+            .filter_map(|id| battle.combatant(id).map(|ally| (id, ally)))
+            .filter(|(_, ally)| ally.health.current() * 2 <= ally.health.max())
+            .min_by_key(|(_, ally)| ally.health.current());
+
+        if let Some((target, _)) = most_injured {
+            let healing_item = battle.combatant(actor)
+                .and_then(|actor| actor.inventory.items().iter()
+                    .position(|item| matches!(item.effect, Some(ItemEffect::Heal(_)))));
+            return match healing_item {
+                Some(item) => BattleAction::UseItem { item, target },
+                None => BattleAction::Heal { target },
+            };
+        }
+        AggressiveAi.choose_action(battle, actor)
+    }
+}
+
+/// A strategy that picks uniformly between defending and attacking any of
+/// the combatant's valid targets. The choices are driven by a [`SeededRng`],
+/// so the same seed always makes the same choices.
+///
+/// # Examples
+///
+/// ```
+/// use druid_game::ai::{CombatAi, RandomAi};
+/// use druid_game::battle::{Battle, CombatantId, Side};
+/// use druid_game::combat::Party;
+/// use druid_game::combatant::Combatant;
+///
+/// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+/// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+/// let battle = Battle::new(allies, enemies);
+/// let vim = CombatantId { side: Side::Enemies, index: 0 };
+///
+/// let mut first = RandomAi::new(7);
+/// let mut second = RandomAi::new(7);
+/// assert_eq!(first.choose_action(&battle, vim), second.choose_action(&battle, vim));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RandomAi {
+    rng: SeededRng,
+}
+
+impl RandomAi {
+    /// Initializes a random strategy from the given seed.
+    pub fn new(seed: u64) -> RandomAi {
+        RandomAi { rng: SeededRng::new(seed) }
+    }
+}
+
+impl CombatAi for RandomAi {
+    fn choose_action(&mut self, battle: &Battle, actor: CombatantId) -> BattleAction {
+        let targets = battle.valid_targets(actor);
+        let choice = self.rng.range(0, targets.len() as i32) as usize;
+        match targets.get(choice) {
+            Some(&target) => BattleAction::Attack { target },
+            None => BattleAction::Defend,
+        }
+    }
+}
+
+/// Has every living member of the given side take a turn, choosing their
+/// actions with the given strategy. Turns stop early if the battle ends.
+///
+/// Each member takes a single action. Their action points aren't restored,
+/// so call [`Battle::start_round`] at the start of each round.
+///
+/// Returns the state of the battle once the turns are over.
+///
+/// # Examples
+///
+/// ```
+/// use druid_game::ai::{self, AggressiveAi};
+/// use druid_game::battle::{Battle, BattleState, Side};
+/// use druid_game::combat::Party;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::service::SeededRng;
+/// use druid_game::weapon::Weapon;
+///
+/// let mut vim = Combatant::new("Vim".to_string());
+/// vim.give_weapon(Weapon::new("Greatsword".to_string(), 100, 20));
+/// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+/// let mut battle = Battle::new(allies, Party::new(vec![vim]));
+///
+/// let mut rng = SeededRng::new(42);
+/// let state = ai::run_turns(&mut battle, &mut rng, Side::Enemies, &mut AggressiveAi);
+/// assert_eq!(BattleState::Won(Side::Enemies), state);
+/// ```
+pub fn run_turns(battle: &mut Battle, rng: &mut dyn RngService, side: Side, ai: &mut dyn CombatAi) -> BattleState {
+    for actor in battle.living(side) {
+        if battle.state() != BattleState::Ongoing {
+            break;
+        }
+        let action = ai.choose_action(battle, actor);
+        // Combatants defeated earlier in the turn can't act, and a strategy
+        // that chooses an invalid action simply loses the turn.
+        let _ = battle.resolve_action(rng, actor, action);
+    }
+    battle.state()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::combat::Party;
+    use crate::combatant::Combatant;
+    use crate::inventory::Item;
+
+    fn test_battle() -> Battle {
+        let allies = Party::new(vec![
+            Combatant::new("Alice".to_string()),
+            Combatant::new("Bob".to_string()),
+        ]);
+        let enemies = Party::new(vec![
+            Combatant::new("Vim".to_string()),
+            Combatant::new("Nano".to_string()),
+        ]);
+        Battle::new(allies, enemies)
+    }
+
+    #[test]
+    fn test_defensive_prefers_items() {
+        let mut battle = test_battle();
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.enemies.members[0].health.damage(5);
+        battle.enemies.members[0].inventory
+            .add_item(Item::with_effect("Herb".to_string(), ItemEffect::Heal(5)))
+            .unwrap();
+
+        assert_eq!(BattleAction::UseItem { item: 0, target: vim },
+            DefensiveAi.choose_action(&battle, vim),
+            "Defensive AI must use a healing item when it has one.");
+    }
+
+    #[test]
+    fn test_defensive_attacks_when_healthy() {
+        let mut battle = test_battle();
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.enemies.members[1].health.damage(4);
+        battle.allies.members[1].health.damage(2);
+
+        assert_eq!(BattleAction::Attack { target: CombatantId { side: Side::Allies, index: 1 } },
+            DefensiveAi.choose_action(&battle, vim),
+            "Defensive AI must only heal allies at or below half health.");
+    }
+
+    #[test]
+    fn test_random_chooses_valid_actions() {
+        let battle = test_battle();
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        let mut ai = RandomAi::new(3);
+        let targets = battle.valid_targets(vim);
+
+        for _ in 0..100 {
+            match ai.choose_action(&battle, vim) {
+                BattleAction::Attack { target } => assert!(targets.contains(&target),
+                    "Random AI must only attack valid targets."),
+                BattleAction::Defend => {},
+                action => panic!("Random AI must not choose {action:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_targets_defends() {
+        let mut battle = test_battle();
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.allies.members[0].health.damage(10);
+        battle.allies.members[1].health.damage(10);
+
+        assert_eq!(BattleAction::Defend, AggressiveAi.choose_action(&battle, vim));
+        assert_eq!(BattleAction::Defend, RandomAi::new(1).choose_action(&battle, vim));
+    }
+}
@@ -1,17 +1,38 @@
 //! This module contains a set of functions for determining the outcome 
-//! of battle.
+//! of battle, as well as the [`Battle`] type for fights between parties.
 
+use std::error::Error;
+use std::fmt::Display;
 use std::ops::Mul;
 
-use crate::combatant::Combatant;
+use crate::combat::Party;
+use crate::combatant::{Combatant, HealthStatus, NotEnoughActionPoints, NotEnoughMana, StatModifier, GUARD_MULTIPLIER};
+use crate::element::{self, Effectiveness};
+use crate::inventory::ItemEffect;
+use crate::loot::LootBundle;
+use crate::progression::XpTable;
+use crate::service::RngService;
+use crate::spell::{Spell, SpellEffect};
+use crate::weapon::Weapon;
+
+pub mod grid;
+pub mod replay;
 
 /// A list specifiying possible results of an attempted attack.
 // TODO: How do you get an attack result?
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum AttackResult {
+    /// The attack struck a vital spot, and will deal amplified damage.
+    CriticalHit,
     /// The attack will deal maximum damage.
     DirectHit,
-    /// The attack will deal half damage.
+    /// The attack narrowly missed a direct hit, and will deal 
+    /// [`graze_multiplier`](BattleRules::graze_multiplier) of full damage 
+    /// (three-quarters by default).
+    Graze,
+    /// The attack will deal 
+    /// [`glancing_multiplier`](BattleRules::glancing_multiplier) of full 
+    /// damage (half by default).
     GlancingBlow,
     /// The attacker missed and dealt no damage. 
     Miss,
@@ -19,6 +40,86 @@ pub enum AttackResult {
     NoWeapon,
 }
 
+/// Rules that tune how [`resolve_attack_with_rules`] interprets a dice
+/// roll, and how [`calculate_damage_with_rules`] scales the result. Each 
+/// [`Battle`] plays by its own [`rules`](Battle::rules), so games can tune 
+/// combat without changing these formulas.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BattleRules {
+    /// The number of sides on the die rolled for attacks and spells in a 
+    /// [`Battle`]. Hit rates are compared against this roll. A die with 
+    /// fewer than one side is treated as one-sided.
+    pub die_sides: i32,
+    /// Whether a roll exactly equal to the hit rate hits, as in "meet it, 
+    /// beat it". Otherwise, rolls must land below the hit rate to hit.
+    pub ties_hit: bool,
+    /// How far above the hit rate a roll may land and still count as a
+    /// [`AttackResult::Graze`] instead of a [`AttackResult::GlancingBlow`].
+    /// 
+    /// A band of `0` disables grazes entirely.
+    pub graze_band: i32,
+    /// The highest roll that counts as a [`AttackResult::CriticalHit`], as 
+    /// long as it would also have hit. 
+    /// 
+    /// A threshold of `0` disables critical hits entirely.
+    pub crit_threshold: i32,
+    /// The multiplier applied to the damage of a 
+    /// [`AttackResult::CriticalHit`].
+    pub crit_multiplier: f64,
+    /// The multiplier applied to the damage of a [`AttackResult::Graze`].
+    pub graze_multiplier: f64,
+    /// The multiplier applied to the damage of a 
+    /// [`AttackResult::GlancingBlow`].
+    pub glancing_multiplier: f64,
+    /// The least damage a damaging attack or spell can deal, if any. Without 
+    /// a minimum, a defense higher than the attack's damage can result in 
+    /// zero or negative damage.
+    pub min_damage: Option<i32>,
+}
+
+impl Default for BattleRules {
+    /// Initializes rules with a hundred-sided die where ties hit, no graze 
+    /// band, critical hits on rolls of 5 or lower dealing double damage, 
+    /// grazes dealing three-quarters damage, glancing blows dealing half 
+    /// damage, and no minimum damage.
+    fn default() -> Self {
+        BattleRules {
+            die_sides: 100,
+            ties_hit: true,
+            graze_band: 0,
+            crit_threshold: 5,
+            crit_multiplier: 2.0,
+            graze_multiplier: 0.75,
+            glancing_multiplier: 0.5,
+            min_damage: None,
+        }
+    }
+}
+
+impl BattleRules {
+    /// Rolls the die given by [`die_sides`](BattleRules::die_sides), from 1 
+    /// up to its number of sides inclusive.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::BattleRules;
+    /// use druid_game::service::SeededRng;
+    /// 
+    /// let mut rng = SeededRng::new(42);
+    /// let rules = BattleRules { die_sides: 6, ..Default::default() };
+    /// assert!((1..=6).contains(&rules.roll(&mut rng)));
+    /// 
+    /// let rules = BattleRules { die_sides: 0, ..Default::default() };
+    /// assert_eq!(1, rules.roll(&mut rng));
+    /// ```
+    pub fn roll(&self, rng: &mut dyn RngService) -> i32 {
+        rng.range(1, self.die_sides.max(1))
+    }
+}
+
 /// Revolves the result of an attack based on a dice roll and the stats of an 
 /// attacker and defender. The provided die roll is compared with a hit rate 
 /// provided by [`calculate_hit_rate`].
@@ -27,7 +128,9 @@ pub enum AttackResult {
 ///  
 /// The dice roll is usually assumed to be between 1 and 100 inclusive.
 /// A low roll on the die is more likely to provide a direct hit, whereas a 
-/// high roll is likely to result in a glancing blow.
+/// high roll is likely to result in a glancing blow. The lowest rolls, 5 or 
+/// under, are critical hits; see [`resolve_attack_with_rules`] to tune 
+/// this.
 /// 
 /// ```
 /// use druid_game::battle;
@@ -77,6 +180,259 @@ pub enum AttackResult {
 /// assert_eq!(battle::AttackResult::NoWeapon, attack_result);
 /// ```
 pub fn resolve_attack(dice_roll: i32, attacker: &Combatant, defender: &Combatant) -> AttackResult {
+    resolve_attack_with_rules(&BattleRules::default(), dice_roll, attacker, defender)
+}
+
+/// Rolls the die given by the rules using the given [`RngService`], then 
+/// resolves the result of the attack with [`resolve_attack_with_rules`].
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::service::SeededRng;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let mut rng = SeededRng::new(42);
+/// let rules = battle::BattleRules::default();
+/// 
+/// // A weapon that can't miss. This seed rolls above the critical hit 
+/// // threshold, so the attack is a direct hit.
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Weapon".to_string(), 100, 10));
+/// let defender = Combatant::new("Defender".to_string());
+/// 
+/// let attack_result = battle::roll_attack(&mut rng, &rules, &attacker, &defender);
+/// assert_eq!(battle::AttackResult::DirectHit, attack_result);
+/// ```
+pub fn roll_attack(rng: &mut dyn RngService, rules: &BattleRules, attacker: &Combatant, defender: &Combatant) -> AttackResult {
+    let dice_roll = rules.roll(rng);
+    resolve_attack_with_rules(rules, dice_roll, attacker, defender)
+}
+
+/// The full outcome of an attack, as returned by [`resolve_attack_full`], so 
+/// that frontends can show a breakdown without recomputing anything.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct AttackOutcome {
+    /// The dice roll the attack was resolved with.
+    pub dice_roll: i32,
+    /// The hit rate the roll was compared against, as given by 
+    /// [`calculate_hit_rate`], or [`Option::None`] if the attacker had no 
+    /// weapon.
+    pub hit_rate: Option<i32>,
+    /// The result of the attack.
+    pub result: AttackResult,
+    /// The weapon damage rolled for the attack, before stats and multipliers 
+    /// are applied, or [`Option::None`] if the attacker had no weapon. See 
+    /// [`Weapon::roll_damage`].
+    pub damage_roll: Option<i32>,
+    /// The damage dealt, if any, as given by [`calculate_damage_with_roll`].
+    pub damage: Option<i32>,
+    /// The defender's health status once the damage is applied.
+    pub defender_status: HealthStatus,
+}
+
+/// Resolves an attack like [`resolve_attack`], and also calculates its 
+/// damage and the defender's resulting health status. Neither combatant is 
+/// changed. 
+/// 
+/// No damage is rolled, so weapons deal their base damage. Attacks made in 
+/// a [`Battle`] roll damage within each weapon's 
+/// [`damage_variance`](Weapon::damage_variance).
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle::{self, AttackResult};
+/// use druid_game::combatant::{Combatant, HealthStatus};
+/// use druid_game::weapon::Weapon;
+/// 
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Sword".to_string(), 50, 10));
+/// let defender = Combatant::new("Defender".to_string());
+/// 
+/// let outcome = battle::resolve_attack_full(40, &attacker, &defender);
+/// assert_eq!(40, outcome.dice_roll);
+/// assert_eq!(Some(50), outcome.hit_rate);
+/// assert_eq!(AttackResult::DirectHit, outcome.result);
+/// assert_eq!(Some(10), outcome.damage_roll);
+/// assert_eq!(Some(10), outcome.damage);
+/// assert_eq!(HealthStatus::Defeated, outcome.defender_status);
+/// 
+/// // The defender hasn't actually taken any damage.
+/// assert_eq!(10, defender.health.current());
+/// ```
+pub fn resolve_attack_full(dice_roll: i32, attacker: &Combatant, defender: &Combatant) -> AttackOutcome {
+    resolve_attack_full_with_rules(&BattleRules::default(), dice_roll, attacker, defender)
+}
+
+/// Resolves an attack like [`resolve_attack_full`], using the given 
+/// [`BattleRules`] like [`resolve_attack_with_rules`] and 
+/// [`calculate_damage_with_rules`].
+pub fn resolve_attack_full_with_rules(rules: &BattleRules, dice_roll: i32, attacker: &Combatant, defender: &Combatant) -> AttackOutcome {
+    resolve_weapon_attack(rules, false, dice_roll, None, attacker, defender)
+}
+
+/// How much the hit rate drops for the extra attack a dual-wielding 
+/// attacker makes with the weapon in their off hand.
+pub const OFF_HAND_PENALTY: i32 = 20;
+
+/// Resolves the extra attack a dual-wielding attacker makes with the weapon 
+/// in their off hand, like [`resolve_attack_full`]. The attack uses the hit 
+/// rate, damage, reach and element of the off hand weapon, and its hit rate 
+/// is lowered by [`OFF_HAND_PENALTY`]. Attackers with no weapon in their off 
+/// hand get [`AttackResult::NoWeapon`].
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle::{self, AttackResult};
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::{OffHand, Weapon};
+/// 
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Shortsword".to_string(), 70, 6));
+/// let defender = Combatant::new("Defender".to_string());
+/// 
+/// let outcome = battle::resolve_off_hand_attack(40, &attacker, &defender);
+/// assert_eq!(AttackResult::NoWeapon, outcome.result);
+/// 
+/// attacker.equip_off_hand(OffHand::Weapon(Weapon::new("Dagger".to_string(), 80, 4))).unwrap();
+/// let outcome = battle::resolve_off_hand_attack(40, &attacker, &defender);
+/// assert_eq!(Some(60), outcome.hit_rate);
+/// assert_eq!(AttackResult::DirectHit, outcome.result);
+/// assert_eq!(Some(4), outcome.damage);
+/// ```
+pub fn resolve_off_hand_attack(dice_roll: i32, attacker: &Combatant, defender: &Combatant) -> AttackOutcome {
+    resolve_off_hand_attack_with_rules(&BattleRules::default(), dice_roll, attacker, defender)
+}
+
+/// Resolves an off hand attack like [`resolve_off_hand_attack`], using the 
+/// given [`BattleRules`].
+pub fn resolve_off_hand_attack_with_rules(rules: &BattleRules, dice_roll: i32, attacker: &Combatant, defender: &Combatant) -> AttackOutcome {
+    resolve_weapon_attack(rules, true, dice_roll, None, attacker, defender)
+}
+
+/// Resolves an attack with the weapon in the attacker's main or off hand 
+/// into an [`AttackOutcome`]. If no damage roll is given, the weapon's base 
+/// damage is used.
+fn resolve_weapon_attack(rules: &BattleRules, off_hand: bool, dice_roll: i32, damage_roll: Option<i32>, attacker: &Combatant, defender: &Combatant) -> AttackOutcome {
+    let (weapon, hit_rate) = match off_hand {
+        false => (attacker.current_weapon().as_ref(), calculate_hit_rate(attacker, defender)),
+        true => (attacker.off_hand_weapon(), calculate_off_hand_hit_rate(attacker, defender)),
+    };
+    let result = match hit_rate {
+        None => AttackResult::NoWeapon,
+        Some(hit_rate) => resolve_roll(rules, dice_roll, hit_rate),
+    };
+    let damage_roll = weapon.map(|weapon| damage_roll.unwrap_or(weapon.damage));
+    let damage = weapon.zip(damage_roll)
+        .and_then(|(weapon, damage_roll)| weapon_damage(rules, weapon, damage_roll, &result, attacker, defender));
+
+    let mut health = defender.health.clone();
+    let defender_status = match damage {
+        Some(damage) => health.apply_damage(damage).status,
+        None => health.check_status(),
+    };
+
+    AttackOutcome {
+        dice_roll,
+        hit_rate,
+        result,
+        damage_roll,
+        damage,
+        defender_status,
+    }
+}
+
+/// Resolves the result of an attack like [`resolve_attack`], using the given 
+/// [`BattleRules`] to interpret the dice roll.
+/// 
+/// # Graze Band
+/// 
+/// Rolls that land above the hit rate, but within the configured 
+/// `graze_band`, result in a [`AttackResult::Graze`]. Only rolls beyond the 
+/// band are glancing blows.
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::battle::BattleRules;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let rules = BattleRules { graze_band: 10, ..Default::default() };
+/// 
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Weapon".to_string(), 50, 10));
+/// let defender = Combatant::new("Defender".to_string()); 
+/// 
+/// // This attack is a graze, because the roll is within 10 of the hit rate.
+/// let dice_roll = 58;
+/// let attack_result = battle::resolve_attack_with_rules(&rules, dice_roll, &attacker, &defender);
+/// assert_eq!(battle::AttackResult::Graze, attack_result);
+/// 
+/// // The edge of the band still counts as a graze.
+/// let dice_roll = 60;
+/// let attack_result = battle::resolve_attack_with_rules(&rules, dice_roll, &attacker, &defender);
+/// assert_eq!(battle::AttackResult::Graze, attack_result);
+/// 
+/// // This attack is a glancing blow, because the roll is beyond the band.
+/// let dice_roll = 61;
+/// let attack_result = battle::resolve_attack_with_rules(&rules, dice_roll, &attacker, &defender);
+/// assert_eq!(battle::AttackResult::GlancingBlow, attack_result);
+/// ```
+/// 
+/// # Critical Hits
+/// 
+/// Rolls at or below the configured `crit_threshold` result in a 
+/// [`AttackResult::CriticalHit`], as long as they are also at or below the 
+/// hit rate.
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::battle::BattleRules;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let rules = BattleRules { crit_threshold: 10, ..Default::default() };
+/// 
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Weapon".to_string(), 50, 10));
+/// let defender = Combatant::new("Defender".to_string()); 
+/// 
+/// let dice_roll = 10;
+/// let attack_result = battle::resolve_attack_with_rules(&rules, dice_roll, &attacker, &defender);
+/// assert_eq!(battle::AttackResult::CriticalHit, attack_result);
+/// 
+/// let dice_roll = 11;
+/// let attack_result = battle::resolve_attack_with_rules(&rules, dice_roll, &attacker, &defender);
+/// assert_eq!(battle::AttackResult::DirectHit, attack_result);
+/// ```
+/// 
+/// # Ties
+/// 
+/// Rolls equal to the hit rate hit, unless `ties_hit` is turned off.
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::battle::BattleRules;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Weapon".to_string(), 50, 10));
+/// let defender = Combatant::new("Defender".to_string()); 
+/// 
+/// let rules = BattleRules::default();
+/// let attack_result = battle::resolve_attack_with_rules(&rules, 50, &attacker, &defender);
+/// assert_eq!(battle::AttackResult::DirectHit, attack_result);
+/// 
+/// let rules = BattleRules { ties_hit: false, ..Default::default() };
+/// let attack_result = battle::resolve_attack_with_rules(&rules, 50, &attacker, &defender);
+/// assert_eq!(battle::AttackResult::GlancingBlow, attack_result);
+/// ```
+pub fn resolve_attack_with_rules(rules: &BattleRules, dice_roll: i32, attacker: &Combatant, defender: &Combatant) -> AttackResult {
     if attacker.current_weapon().is_none() {
         return AttackResult::NoWeapon;
     }
@@ -85,10 +441,26 @@ pub fn resolve_attack(dice_roll: i32, attacker: &Combatant, defender: &Combatant
         None => return AttackResult::Miss, // Automatic miss
         Some(hit_rate) => hit_rate,
     };
+    resolve_roll(rules, dice_roll, hit_rate)
+}
+
+/// Compares a dice roll against a hit rate to find the result of an attack 
+/// or spell.
+fn resolve_roll(rules: &BattleRules, dice_roll: i32, hit_rate: i32) -> AttackResult {
+    let hits = match rules.ties_hit {
+        true => dice_roll <= hit_rate,
+        false => dice_roll < hit_rate,
+    };
 
-    if dice_roll <= hit_rate {
+    if hits && dice_roll <= rules.crit_threshold {
+        AttackResult::CriticalHit
+    }
+    else if hits {
         AttackResult::DirectHit
     }
+    else if rules.graze_band > 0 && dice_roll <= hit_rate + rules.graze_band {
+        AttackResult::Graze
+    }
     else {
         AttackResult::GlancingBlow
     }
@@ -168,6 +540,25 @@ pub fn resolve_attack(dice_roll: i32, attacker: &Combatant, defender: &Combatant
 /// assert_eq!(Some(60), hit_rate);
 /// ``` 
 /// 
+/// # Attacker Morale
+/// 
+/// The attacker's morale is added to the hit rate, so a shaken attacker is 
+/// less likely to hit.
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+///
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Weapon".to_string(), 50, 5));
+/// let defender = Combatant::new("Defender".to_string());
+///
+/// attacker.morale = -10;
+/// let hit_rate = battle::calculate_hit_rate(&attacker, &defender);
+/// assert_eq!(Some(40), hit_rate);
+/// ```
+/// 
 /// # Defender Evasion
 /// 
 /// Increasing the defender's evasion score will negatively affect the 
@@ -192,21 +583,103 @@ pub fn resolve_attack(dice_roll: i32, attacker: &Combatant, defender: &Combatant
 /// let hit_rate = battle::calculate_hit_rate(&attacker, &defender);
 /// assert_eq!(Some(40), hit_rate);
 /// ```
+/// 
+/// # Range
+/// 
+/// Attacking a defender beyond the reach of the attacker's weapon lowers the 
+/// hit rate by [`OUT_OF_RANGE_PENALTY`] for each step too far. See 
+/// [`distance`].
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combat::Row;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+///
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Weapon".to_string(), 50, 5));
+/// let mut defender = Combatant::new("Defender".to_string());
+///
+/// defender.row = Row::Back;
+/// let hit_rate = battle::calculate_hit_rate(&attacker, &defender);
+/// assert_eq!(Some(30), hit_rate);
+/// ```
 pub fn calculate_hit_rate(attacker: &Combatant, defender: &Combatant) -> Option<i32> {
-    let mut hit_rate = match attacker.current_weapon() {
-        None => return None,
-        Some(weapon) => weapon.hit_rate,
-    };
+    weapon_hit_rate(attacker.current_weapon().as_ref(), attacker, defender)
+}
+
+/// Calculates the hit rate of the extra attack a dual-wielding attacker 
+/// makes with the weapon in their off hand. This works like 
+/// [`calculate_hit_rate`] using the off hand weapon, lowered by 
+/// [`OFF_HAND_PENALTY`]. Returns [`Option::None`] if there is no weapon in 
+/// the attacker's off hand.
+pub fn calculate_off_hand_hit_rate(attacker: &Combatant, defender: &Combatant) -> Option<i32> {
+    weapon_hit_rate(attacker.off_hand_weapon(), attacker, defender)
+        .map(|hit_rate| hit_rate - OFF_HAND_PENALTY)
+}
+
+/// Calculates the hit rate of an attack made with the given weapon.
+fn weapon_hit_rate(weapon: Option<&Weapon>, attacker: &Combatant, defender: &Combatant) -> Option<i32> {
+    let mut hit_rate = weapon?.hit_rate;
 
     // Attacker accuracy
-    hit_rate += attacker.stats.accuracy;
+    hit_rate += attacker.effective_stats().accuracy;
+
+    // Attacker morale
+    hit_rate += attacker.morale;
 
     // Defender
-    hit_rate -= defender.stats.evasion;
+    hit_rate -= defender.effective_stats().evasion;
+
+    // Range
+    hit_rate -= weapon_range_penalty(weapon, attacker, defender);
 
     Some(hit_rate)
 }
 
+/// How much the hit rate drops for each step a defender is beyond the reach 
+/// of the attacker's weapon.
+pub const OUT_OF_RANGE_PENALTY: i32 = 20;
+
+/// Returns how many steps apart two combatants on opposing sides stand. 
+/// Combatants in the front rows are 1 apart, and each combatant in a back 
+/// row adds 1 more.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combat::Row;
+/// use druid_game::combatant::Combatant;
+/// 
+/// let mut archer = Combatant::new("Archer".to_string());
+/// let mut target = Combatant::new("Target".to_string());
+/// assert_eq!(1, battle::distance(&archer, &target));
+/// 
+/// archer.row = Row::Back;
+/// target.row = Row::Back;
+/// assert_eq!(3, battle::distance(&archer, &target));
+/// ```
+pub fn distance(attacker: &Combatant, defender: &Combatant) -> u32 {
+    1 + attacker.row.depth() + defender.row.depth()
+}
+
+/// Returns how much the hit rate of an attack drops because the defender is 
+/// beyond the reach of the attacker's weapon. Attacks without a weapon have 
+/// no penalty.
+pub fn calculate_range_penalty(attacker: &Combatant, defender: &Combatant) -> i32 {
+    weapon_range_penalty(attacker.current_weapon().as_ref(), attacker, defender)
+}
+
+/// Returns the range penalty of an attack made with the given weapon.
+fn weapon_range_penalty(weapon: Option<&Weapon>, attacker: &Combatant, defender: &Combatant) -> i32 {
+    let Some(weapon) = weapon else {
+        return 0;
+    };
+    let steps_too_far = distance(attacker, defender).saturating_sub(weapon.reach);
+    OUT_OF_RANGE_PENALTY * steps_too_far as i32
+}
+
 /// Calculates the damage of an attack based on attack result and the stats of 
 /// the attacker and defender. 
 /// 
@@ -214,9 +687,11 @@ pub fn calculate_hit_rate(attacker: &Combatant, defender: &Combatant) -> Option<
 /// 
 /// The `attack_result` parameter has a variety of impacts on the damage dealt.
 /// 
-/// If the `attack_result` is [`AttackResult::DirectHit`], the calculation 
-/// will result in full damage, whereas [`AttackResult::GlancingBlow`] 
-/// results in half damage.
+/// If the `attack_result` is [`AttackResult::CriticalHit`], the calculation 
+/// will result in double damage. [`AttackResult::DirectHit`] results in full 
+/// damage, [`AttackResult::Graze`] results in 
+/// three-quarters damage, and [`AttackResult::GlancingBlow`] results in half 
+/// damage.
 /// 
 /// ```
 /// use druid_game::battle;
@@ -227,10 +702,18 @@ pub fn calculate_hit_rate(attacker: &Combatant, defender: &Combatant) -> Option<
 /// attacker.give_weapon(Weapon::new("Dummy Sword".to_string(), 50, 10));
 /// let defender = Combatant::new("Defender".to_string());
 /// 
+/// let attack_result = battle::AttackResult::CriticalHit;
+/// let damage = battle::calculate_damage(&attack_result, &attacker, &defender);
+/// assert_eq!(Some(20), damage);
+/// 
 /// let attack_result = battle::AttackResult::DirectHit;
 /// let damage = battle::calculate_damage(&attack_result, &attacker, &defender);
 /// assert_eq!(Some(10), damage);
 /// 
+/// let attack_result = battle::AttackResult::Graze;
+/// let damage = battle::calculate_damage(&attack_result, &attacker, &defender);
+/// assert_eq!(Some(7), damage);
+/// 
 /// let attack_result = battle::AttackResult::GlancingBlow;
 /// let damage = battle::calculate_damage(&attack_result, &attacker, &defender);
 /// assert_eq!(Some(5), damage);
@@ -301,25 +784,2444 @@ pub fn calculate_hit_rate(attacker: &Combatant, defender: &Combatant) -> Option<
 /// assert_eq!(Some(5), damage);
 /// ```
 /// 
+/// # Guarding
+/// 
+/// If the defender is [`guarding`](Combatant::guarding), the damage is 
+/// multiplied by [`GUARD_MULTIPLIER`](crate::combatant::GUARD_MULTIPLIER). 
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let attack_result = battle::AttackResult::DirectHit;
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Sword".to_string(), 50, 10));
+/// let mut defender = Combatant::new("Defender".to_string());
+/// 
+/// defender.guarding = true;
+/// let damage = battle::calculate_damage(&attack_result, &attacker, &defender);
+/// assert_eq!(Some(5), damage);
+/// ```
+/// 
 pub fn calculate_damage(attack_result: &AttackResult, attacker: &Combatant, defender: &Combatant) -> Option<i32> {
+    calculate_damage_with_rules(&BattleRules::default(), attack_result, attacker, defender)
+}
+
+/// Calculates the damage of an attack like [`calculate_damage`], using the 
+/// given [`BattleRules`] to scale the result and apply any minimum damage. 
+/// 
+/// Damage is also scaled by the [`attack_effectiveness`] of the attacker's 
+/// weapon against the defender's element.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::battle::{AttackResult, BattleRules};
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let rules = BattleRules { crit_multiplier: 3.0, ..Default::default() };
+/// 
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Sword".to_string(), 50, 10));
+/// let defender = Combatant::new("Defender".to_string());
+/// 
+/// let attack_result = AttackResult::CriticalHit;
+/// let damage = battle::calculate_damage_with_rules(&rules, &attack_result, &attacker, &defender);
+/// assert_eq!(Some(30), damage);
+/// 
+/// let rules = BattleRules { min_damage: Some(1), ..Default::default() };
+/// let mut defender = Combatant::new("Defender".to_string());
+/// defender.stats.defense = 20;
+/// let damage = battle::calculate_damage_with_rules(&rules, &attack_result, &attacker, &defender);
+/// assert_eq!(Some(1), damage);
+/// ```
+pub fn calculate_damage_with_rules(rules: &BattleRules, attack_result: &AttackResult, attacker: &Combatant, defender: &Combatant) -> Option<i32> {
+    let weapon = attacker.current_weapon().as_ref()?;
+    weapon_damage(rules, weapon, weapon.damage, attack_result, attacker, defender)
+}
+
+/// Calculates the damage of an attack like [`calculate_damage_with_rules`], 
+/// using a damage roll from [`Weapon::roll_damage`] in place of the weapon's 
+/// base damage. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle::{self, AttackResult, BattleRules};
+/// use druid_game::combatant::Combatant;
+/// use druid_game::service::SeededRng;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let mut sword = Weapon::new("Dummy Sword".to_string(), 50, 10);
+/// sword.damage_variance = 2;
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(sword.clone());
+/// let defender = Combatant::new("Defender".to_string());
+/// 
+/// let rules = BattleRules::default();
+/// let attack_result = AttackResult::DirectHit;
+/// let damage = battle::calculate_damage_with_roll(&rules, &attack_result, 12, &attacker, &defender);
+/// assert_eq!(Some(12), damage);
+/// 
+/// let mut rng = SeededRng::new(42);
+/// let damage_roll = sword.roll_damage(&mut rng);
+/// assert!((8..=12).contains(&damage_roll));
+/// ```
+pub fn calculate_damage_with_roll(rules: &BattleRules, attack_result: &AttackResult, damage_roll: i32, attacker: &Combatant, defender: &Combatant) -> Option<i32> {
+    let weapon = attacker.current_weapon().as_ref()?;
+    weapon_damage(rules, weapon, damage_roll, attack_result, attacker, defender)
+}
+
+/// Calculates the damage of an attack made with the given weapon, starting 
+/// from the given damage roll.
+fn weapon_damage(rules: &BattleRules, weapon: &Weapon, damage_roll: i32, attack_result: &AttackResult, attacker: &Combatant, defender: &Combatant) -> Option<i32> {
     // Attack effectiveness multiplier
-    let multiplier = match attack_result {
-        AttackResult::Miss => return None,
-        AttackResult::NoWeapon => return None,
-        AttackResult::DirectHit => 1.0,
-        AttackResult::GlancingBlow => 0.5, 
-    };
+    let multiplier = damage_multiplier(rules, attack_result)?;
 
     // Calculate base damage
-    let mut damage = match attacker.current_weapon() {
-        None => return None,
-        Some(weapon) => weapon.damage,
-    };
-    damage += attacker.stats.strength;
-    damage -= defender.stats.defense;
+    let mut damage = damage_roll;
+    damage += attacker.effective_stats().strength;
+    damage -= defender.effective_stats().defense;
+
+    // Multipliers
+    let effectiveness = weapon_effectiveness(Some(weapon), defender);
+    let damage = (damage as f64).mul(multiplier).mul(effectiveness.multiplier());
+    let damage = guard_damage(damage, defender);
+
+    Some(apply_min_damage(rules, damage as i32))
+}
+
+/// Scales incoming damage by [`GUARD_MULTIPLIER`] if the defender is 
+/// [`guarding`](Combatant::guarding). Every source of damage goes through 
+/// this, so guarding protects against spells as well as weapons.
+fn guard_damage(damage: f64, defender: &Combatant) -> f64 {
+    if defender.guarding {
+        damage * GUARD_MULTIPLIER
+    }
+    else {
+        damage
+    }
+}
 
-    // Multiplier
-    let damage = (damage as f64).mul(multiplier);
+/// Raises damage to the minimum allowed by the rules, if any.
+fn apply_min_damage(rules: &BattleRules, damage: i32) -> i32 {
+    match rules.min_damage {
+        None => damage,
+        Some(min_damage) => damage.max(min_damage),
+    }
+}
 
-    Some(damage as i32)
-}
\ No newline at end of file
+/// Returns how effective the element of the attacker's weapon is against 
+/// the defender's element, for hinting at good matchups before attacking.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::element::{Effectiveness, Element};
+/// use druid_game::weapon::Weapon;
+/// 
+/// let mut torch = Weapon::new("Torch".to_string(), 60, 6);
+/// torch.element = Some(Element::Fire);
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(torch);
+/// let mut defender = Combatant::new("Treant".to_string());
+/// defender.element = Some(Element::Nature);
+/// 
+/// assert_eq!(Effectiveness::Strong, battle::attack_effectiveness(&attacker, &defender));
+/// assert_eq!(Some(9), battle::calculate_damage(&battle::AttackResult::DirectHit, &attacker, &defender));
+/// ```
+pub fn attack_effectiveness(attacker: &Combatant, defender: &Combatant) -> Effectiveness {
+    weapon_effectiveness(attacker.current_weapon().as_ref(), defender)
+}
+
+/// Returns how effective the element of the given weapon is against the 
+/// defender's element.
+fn weapon_effectiveness(weapon: Option<&Weapon>, defender: &Combatant) -> Effectiveness {
+    let attack_element = weapon.and_then(|weapon| weapon.element);
+    element::effectiveness(attack_element, defender.element)
+}
+
+/// Returns how effective a spell's element is against the target's element. 
+/// Healing spells are always neutral.
+pub fn spell_effectiveness(spell: &Spell, target: &Combatant) -> Effectiveness {
+    match spell.effect {
+        SpellEffect::Heal => Effectiveness::Neutral,
+        SpellEffect::Damage => element::effectiveness(spell.element, target.element),
+    }
+}
+
+/// Returns the multiplier applied to an attack's damage for the given attack 
+/// result, or [`Option::None`] if the attack deals no damage.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::battle::{AttackResult, BattleRules};
+/// 
+/// let rules = BattleRules::default();
+/// assert_eq!(Some(2.0), battle::damage_multiplier(&rules, &AttackResult::CriticalHit));
+/// assert_eq!(Some(1.0), battle::damage_multiplier(&rules, &AttackResult::DirectHit));
+/// assert_eq!(Some(0.5), battle::damage_multiplier(&rules, &AttackResult::GlancingBlow));
+/// assert_eq!(None, battle::damage_multiplier(&rules, &AttackResult::Miss));
+/// ```
+pub fn damage_multiplier(rules: &BattleRules, attack_result: &AttackResult) -> Option<f64> {
+    match attack_result {
+        AttackResult::Miss => None,
+        AttackResult::NoWeapon => None,
+        AttackResult::CriticalHit => Some(rules.crit_multiplier),
+        AttackResult::DirectHit => Some(1.0),
+        AttackResult::Graze => Some(rules.graze_multiplier),
+        AttackResult::GlancingBlow => Some(rules.glancing_multiplier), 
+    }
+}
+
+/// Calculates the chance of a damaging spell hitting its target. This works 
+/// like [`calculate_hit_rate`], using the spell's hit rate in place of a 
+/// weapon's.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::spell::Spell;
+/// 
+/// let mut caster = Combatant::new("Caster".to_string());
+/// caster.stats.accuracy = 5;
+/// let mut target = Combatant::new("Target".to_string());
+/// target.stats.evasion = 10;
+/// 
+/// let bolt = Spell::damage("Bolt".to_string(), 3, 80, 6);
+/// assert_eq!(75, battle::calculate_spell_hit_rate(&bolt, &caster, &target));
+/// ```
+pub fn calculate_spell_hit_rate(spell: &Spell, caster: &Combatant, target: &Combatant) -> i32 {
+    spell.hit_rate 
+        + caster.effective_stats().accuracy 
+        + caster.morale 
+        - target.effective_stats().evasion
+}
+
+/// Resolves the result of casting a spell based on a dice roll, in the same 
+/// way as [`resolve_attack`]. Healing spells always result in a 
+/// [`AttackResult::DirectHit`].
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle::{self, AttackResult};
+/// use druid_game::combatant::Combatant;
+/// use druid_game::spell::Spell;
+/// 
+/// let caster = Combatant::new("Caster".to_string());
+/// let target = Combatant::new("Target".to_string());
+/// 
+/// let bolt = Spell::damage("Bolt".to_string(), 3, 50, 6);
+/// assert_eq!(AttackResult::DirectHit, battle::resolve_spell(40, &bolt, &caster, &target));
+/// assert_eq!(AttackResult::GlancingBlow, battle::resolve_spell(60, &bolt, &caster, &target));
+/// 
+/// let mend = Spell::heal("Mend".to_string(), 2, 5);
+/// assert_eq!(AttackResult::DirectHit, battle::resolve_spell(100, &mend, &caster, &target));
+/// ```
+pub fn resolve_spell(dice_roll: i32, spell: &Spell, caster: &Combatant, target: &Combatant) -> AttackResult {
+    resolve_spell_with_rules(&BattleRules::default(), dice_roll, spell, caster, target)
+}
+
+/// Resolves the result of casting a spell like [`resolve_spell`], using the 
+/// given [`BattleRules`] to interpret the dice roll.
+pub fn resolve_spell_with_rules(rules: &BattleRules, dice_roll: i32, spell: &Spell, caster: &Combatant, target: &Combatant) -> AttackResult {
+    match spell.effect {
+        SpellEffect::Heal => AttackResult::DirectHit,
+        SpellEffect::Damage => {
+            let hit_rate = calculate_spell_hit_rate(spell, caster, target);
+            resolve_roll(rules, dice_roll, hit_rate)
+        },
+    }
+}
+
+/// Calculates how much damage a spell deals, or how much health it restores, 
+/// for the given result. The spell's power is scaled by the same multipliers 
+/// as an attack, including its [`spell_effectiveness`] against the target, 
+/// but neither the caster's strength nor the target's defense apply.
+/// 
+/// Returns [`Option::None`] if the spell missed.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle::{self, AttackResult};
+/// use druid_game::combatant::Combatant;
+/// use druid_game::spell::Spell;
+/// 
+/// let target = Combatant::new("Target".to_string());
+/// let bolt = Spell::damage("Bolt".to_string(), 3, 80, 6);
+/// assert_eq!(Some(12), battle::calculate_spell_power(&AttackResult::CriticalHit, &bolt, &target));
+/// assert_eq!(Some(3), battle::calculate_spell_power(&AttackResult::GlancingBlow, &bolt, &target));
+/// assert_eq!(None, battle::calculate_spell_power(&AttackResult::Miss, &bolt, &target));
+/// ```
+pub fn calculate_spell_power(attack_result: &AttackResult, spell: &Spell, target: &Combatant) -> Option<i32> {
+    calculate_spell_power_with_rules(&BattleRules::default(), attack_result, spell, target)
+}
+
+/// Calculates the power of a spell like [`calculate_spell_power`], using the 
+/// given [`BattleRules`] to scale the result. Damaging spells are halved 
+/// against a [`guarding`](Combatant::guarding) target like weapon attacks 
+/// are, and the minimum damage applies to them only.
+pub fn calculate_spell_power_with_rules(rules: &BattleRules, attack_result: &AttackResult, spell: &Spell, target: &Combatant) -> Option<i32> {
+    let multiplier = damage_multiplier(rules, attack_result)?;
+    let effectiveness = spell_effectiveness(spell, target);
+    let power = (spell.power as f64).mul(multiplier).mul(effectiveness.multiplier());
+    match spell.effect {
+        SpellEffect::Damage => Some(apply_min_damage(rules, guard_damage(power, target) as i32)),
+        SpellEffect::Heal => Some(power as i32),
+    }
+}
+
+/// Calculates the chance, out of 100, of a combatant escaping from battle 
+/// while being chased by the given pursuer. The chance starts at 50, and 
+/// rises or falls by 10 for each point of effective speed the runner has 
+/// over or under the pursuer.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combatant::Combatant;
+/// 
+/// let mut runner = Combatant::new("Runner".to_string());
+/// let mut pursuer = Combatant::new("Pursuer".to_string());
+/// assert_eq!(50, battle::calculate_escape_chance(&runner, &pursuer));
+/// 
+/// runner.stats.speed = 3;
+/// pursuer.stats.speed = 1;
+/// assert_eq!(70, battle::calculate_escape_chance(&runner, &pursuer));
+/// 
+/// pursuer.stats.speed = 10;
+/// assert_eq!(0, battle::calculate_escape_chance(&runner, &pursuer));
+/// ```
+pub fn calculate_escape_chance(runner: &Combatant, pursuer: &Combatant) -> i32 {
+    let speed_difference = runner.effective_stats().speed - pursuer.effective_stats().speed;
+    (50 + 10 * speed_difference).clamp(0, 100)
+}
+
+/// An itemized breakdown of a hypothetical attack, as returned by 
+/// [`analyze_attack`]. 
+#[derive(PartialEq, Debug)]
+pub struct AttackAnalysis {
+    /// The hit rate of the attacker's weapon.
+    pub weapon_hit_rate: i32,
+    /// The hit rate added by the attacker's accuracy.
+    pub accuracy_bonus: i32,
+    /// The hit rate added by the attacker's morale.
+    pub morale_bonus: i32,
+    /// The hit rate removed by the defender's evasion.
+    pub evasion_penalty: i32,
+    /// The hit rate removed by the defender being out of the weapon's reach.
+    pub range_penalty: i32,
+    /// The final hit rate, as given by [`calculate_hit_rate`].
+    pub hit_rate: i32,
+    /// The damage of the attacker's weapon.
+    pub weapon_damage: i32,
+    /// The damage added by the attacker's strength.
+    pub strength_bonus: i32,
+    /// The damage removed by the defender's defense.
+    pub defense_reduction: i32,
+    /// The damage before any attack result multiplier is applied.
+    pub base_damage: i32,
+    /// How effective the attacker's weapon is against the defender's 
+    /// element.
+    pub effectiveness: Effectiveness,
+    /// What the damage is multiplied by because the defender is 
+    /// [`guarding`](Combatant::guarding), or `1.0` if they aren't.
+    pub guard_multiplier: f64,
+    /// The final damage of a [`AttackResult::CriticalHit`].
+    pub critical_hit_damage: i32,
+    /// The final damage of a [`AttackResult::DirectHit`].
+    pub direct_hit_damage: i32,
+    /// The final damage of a [`AttackResult::Graze`].
+    pub graze_damage: i32,
+    /// The final damage of a [`AttackResult::GlancingBlow`].
+    pub glancing_blow_damage: i32,
+}
+
+/// Breaks down every contribution to the hit rate and damage of an attack, 
+/// without rolling for it. This shows the work behind 
+/// [`calculate_hit_rate`] and [`calculate_damage_with_rules`] under the 
+/// given [`BattleRules`], for use in tooltips and when debugging formula 
+/// changes. Pass a battle's [`rules`](Battle::rules) to get the damage it 
+/// would actually deal.
+/// 
+/// Returns [`Option::None`] if the attacker isn't wielding a weapon.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle::{self, BattleRules};
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let rules = BattleRules::default();
+/// let mut attacker = Combatant::new("Attacker".to_string());
+/// attacker.give_weapon(Weapon::new("Dummy Sword".to_string(), 70, 10));
+/// attacker.stats.strength = 4;
+/// let mut defender = Combatant::new("Defender".to_string());
+/// defender.stats.defense = 2;
+/// 
+/// let analysis = battle::analyze_attack(&rules, &attacker, &defender).unwrap();
+/// assert_eq!(70, analysis.hit_rate);
+/// assert_eq!(4, analysis.strength_bonus);
+/// assert_eq!(2, analysis.defense_reduction);
+/// assert_eq!(12, analysis.direct_hit_damage);
+/// assert_eq!(6, analysis.glancing_blow_damage);
+/// 
+/// defender.guarding = true;
+/// let analysis = battle::analyze_attack(&rules, &attacker, &defender).unwrap();
+/// assert_eq!(0.5, analysis.guard_multiplier);
+/// assert_eq!(6, analysis.direct_hit_damage);
+/// ```
+pub fn analyze_attack(rules: &BattleRules, attacker: &Combatant, defender: &Combatant) -> Option<AttackAnalysis> {
+    let weapon = attacker.current_weapon().as_ref()?;
+    let attacker_stats = attacker.effective_stats();
+    let defender_stats = defender.effective_stats();
+
+    let hit_rate = calculate_hit_rate(attacker, defender)?;
+    let damage_for = |attack_result| calculate_damage_with_rules(rules, &attack_result, attacker, defender);
+
+    Some(AttackAnalysis {
+        weapon_hit_rate: weapon.hit_rate,
+        accuracy_bonus: attacker_stats.accuracy,
+        morale_bonus: attacker.morale,
+        evasion_penalty: defender_stats.evasion,
+        range_penalty: calculate_range_penalty(attacker, defender),
+        hit_rate,
+        weapon_damage: weapon.damage,
+        strength_bonus: attacker_stats.strength,
+        defense_reduction: defender_stats.defense,
+        base_damage: weapon.damage + attacker_stats.strength - defender_stats.defense,
+        effectiveness: attack_effectiveness(attacker, defender),
+        guard_multiplier: guard_damage(1.0, defender),
+        critical_hit_damage: damage_for(AttackResult::CriticalHit)?,
+        direct_hit_damage: damage_for(AttackResult::DirectHit)?,
+        graze_damage: damage_for(AttackResult::Graze)?,
+        glancing_blow_damage: damage_for(AttackResult::GlancingBlow)?,
+    })
+}
+
+/// The two sides of a [`Battle`].
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+    /// The player's party.
+    Allies,
+    /// The party opposing the player.
+    Enemies,
+}
+
+impl Side {
+    /// Returns the side that this side fights against.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::Side;
+    /// 
+    /// assert_eq!(Side::Enemies, Side::Allies.opponent());
+    /// ```
+    pub fn opponent(self) -> Side {
+        match self {
+            Side::Allies => Side::Enemies,
+            Side::Enemies => Side::Allies,
+        }
+    }
+}
+
+/// Identifies a combatant in a [`Battle`] by their side and their position 
+/// among their party's members.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CombatantId {
+    /// The side the combatant fights on.
+    pub side: Side,
+    /// The combatant's index in their party's members.
+    pub index: usize,
+}
+
+/// How much health the [`BattleAction::Heal`] action restores.
+pub const HEAL_AMOUNT: i32 = 5;
+
+/// Something a combatant can do on their turn in a [`Battle`].
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BattleAction {
+    /// Attack an opponent with the equipped weapon. Dual-wielding combatants 
+    /// follow up with an [`off_hand_attack`](Battle::off_hand_attack) if the 
+    /// opponent is still standing.
+    Attack {
+        /// The combatant to attack.
+        target: CombatantId,
+    },
+    /// Restore [`HEAL_AMOUNT`] health to themself or an ally.
+    Heal {
+        /// The combatant to heal.
+        target: CombatantId,
+    },
+    /// Brace for attacks, [`guarding`](Combatant::guarding) against the 
+    /// next hit.
+    Defend,
+    /// Try to flee the battle with their whole party. Success depends on 
+    /// the [`escape_chance`](Battle::escape_chance).
+    Escape,
+    /// Cast a spell they know, spending its mana cost. Damaging spells target 
+    /// an opponent, and healing spells target themself or an ally.
+    Cast {
+        /// The index of the spell among the caster's known spells.
+        spell: usize,
+        /// The combatant to cast the spell on.
+        target: CombatantId,
+    },
+    /// Use an item from their inventory on themself or an ally, consuming 
+    /// it.
+    UseItem {
+        /// The index of the item among the user's inventory items.
+        item: usize,
+        /// The combatant to use the item on.
+        target: CombatantId,
+    },
+}
+
+impl BattleAction {
+    /// Returns how many action points the action costs. Attacks and spells 
+    /// cost 2, and everything else costs 1.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{BattleAction, CombatantId, Side};
+    /// 
+    /// let vim = CombatantId { side: Side::Enemies, index: 0 };
+    /// assert_eq!(2, BattleAction::Attack { target: vim }.cost());
+    /// assert_eq!(1, BattleAction::Defend.cost());
+    /// ```
+    pub fn cost(&self) -> u32 {
+        match self {
+            BattleAction::Attack { .. } | BattleAction::Cast { .. } => 2,
+            BattleAction::Heal { .. } 
+                | BattleAction::Defend 
+                | BattleAction::Escape 
+                | BattleAction::UseItem { .. } => 1,
+        }
+    }
+}
+
+/// The progress of a [`Battle`].
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum BattleState {
+    /// Both sides still have combatants standing.
+    Ongoing,
+    /// Every combatant on the other side has been defeated.
+    Won(Side),
+    /// The given side escaped from the battle.
+    Fled(Side),
+}
+
+/// Something that happened during a [`Battle`], recorded so that frontends 
+/// can display or log it however they like.
+#[derive(PartialEq, Debug, Clone)]
+pub enum BattleEvent {
+    /// A combatant began an attack.
+    AttackDeclared {
+        /// The combatant making the attack.
+        attacker: CombatantId,
+        /// The combatant being attacked.
+        target: CombatantId,
+    },
+    /// An attack's result was rolled.
+    AttackResolved {
+        /// The combatant making the attack.
+        attacker: CombatantId,
+        /// The combatant being attacked.
+        target: CombatantId,
+        /// The result of the attack.
+        result: AttackResult,
+    },
+    /// A combatant took damage.
+    Damaged {
+        /// The combatant that took damage.
+        target: CombatantId,
+        /// How much damage they took.
+        damage: i32,
+        /// How much health they have left.
+        remaining: i32,
+    },
+    /// A combatant cast a spell. The result of a damaging spell is recorded 
+    /// by a following [`BattleEvent::AttackResolved`]. Healing spells always 
+    /// take hold, so they go straight to a [`BattleEvent::Healed`].
+    SpellCast {
+        /// The combatant casting the spell.
+        caster: CombatantId,
+        /// The name of the spell.
+        spell: String,
+        /// The combatant the spell was cast on.
+        target: CombatantId,
+    },
+    /// A combatant regained health.
+    Healed {
+        /// The combatant that was healed.
+        target: CombatantId,
+        /// How much health they regained.
+        amount: i32,
+        /// How much health they have now.
+        remaining: i32,
+    },
+    /// A combatant led their party in escaping the battle, ending it.
+    Fled {
+        /// The combatant that found a way out.
+        actor: CombatantId,
+    },
+    /// A combatant tried to escape the battle, but was cut off.
+    EscapeFailed {
+        /// The combatant that tried to escape.
+        actor: CombatantId,
+    },
+    /// A combatant braced for attacks.
+    Defending {
+        /// The combatant that is defending.
+        target: CombatantId,
+    },
+    /// A combatant used an item.
+    ItemUsed {
+        /// The combatant that used the item.
+        user: CombatantId,
+        /// The name of the item.
+        item: String,
+        /// The combatant the item was used on.
+        target: CombatantId,
+    },
+    /// A buff or debuff on a combatant ran out.
+    ModifierExpired {
+        /// The combatant the modifier was on.
+        target: CombatantId,
+        /// The modifier that ran out.
+        modifier: StatModifier,
+    },
+    /// A combatant was defeated.
+    Defeated {
+        /// The combatant that was defeated.
+        target: CombatantId,
+        /// How much damage exceeded what was needed to defeat them.
+        overkill: i32,
+    },
+    /// Every combatant on one side was defeated, ending the battle.
+    BattleEnded {
+        /// The side left standing.
+        winner: Side,
+    },
+    /// A combatant earned experience.
+    ExperienceGained {
+        /// The combatant that earned experience.
+        target: CombatantId,
+        /// How much experience they earned.
+        amount: u32,
+    },
+    /// A combatant reached a new level.
+    LevelUp {
+        /// The combatant that leveled up.
+        target: CombatantId,
+        /// Their new level.
+        level: u32,
+    },
+}
+
+/// A list of reasons an action in a [`Battle`] can be rejected.
+#[derive(PartialEq, Debug)]
+pub enum BattleError {
+    /// The acting combatant doesn't exist or has been defeated.
+    InvalidActor(CombatantId),
+    /// The target doesn't exist, has been defeated, or is on the same side 
+    /// as the actor. 
+    InvalidTarget(CombatantId),
+    /// The actor has no item at the given index, or it can't be used.
+    InvalidItem(usize),
+    /// The actor knows no spell at the given index.
+    InvalidSpell(usize),
+    /// The actor can't afford the spell's mana cost.
+    NotEnoughMana(NotEnoughMana),
+    /// The actor can't afford the action's [`cost`](BattleAction::cost) 
+    /// this round.
+    NotEnoughActionPoints(NotEnoughActionPoints),
+    /// The battle has already ended.
+    BattleOver,
+}
+impl Display for BattleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BattleError::InvalidActor(id) => write!(f, "{id:?} can't act"),
+            BattleError::InvalidTarget(id) => write!(f, "{id:?} can't be targeted"),
+            BattleError::InvalidItem(index) => write!(f, "Item {index} can't be used"),
+            BattleError::InvalidSpell(index) => write!(f, "Spell {index} isn't known"),
+            BattleError::NotEnoughMana(error) => write!(f, "Can't cast: {error}"),
+            BattleError::NotEnoughActionPoints(error) => write!(f, "Can't act: {error}"),
+            BattleError::BattleOver => write!(f, "The battle is over"),
+        }
+    }
+}
+impl Error for BattleError {}
+
+/// A fight between two parties. 
+/// 
+/// Each attack is resolved between a single attacker and defender, using 
+/// [`roll_attack`] and [`calculate_damage`], while the battle keeps track of
+/// who is left standing on each side. Everything that happens is recorded as
+/// a [`BattleEvent`], to be collected with [`take_events`](Battle::take_events).
+pub struct Battle {
+    /// The player's party.
+    pub allies: Party,
+    /// The party opposing the player.
+    pub enemies: Party,
+    /// The rules attacks and spells are resolved by.
+    pub rules: BattleRules,
+    events: Vec<BattleEvent>,
+    fled: Option<Side>,
+    xp_awarded: bool,
+    loot: Option<LootBundle>,
+}
+
+impl Battle {
+    /// Initializes a battle between the two given parties, played by the 
+    /// default [`BattleRules`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, BattleState};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// 
+    /// let battle = Battle::new(allies, enemies);
+    /// assert_eq!(BattleState::Ongoing, battle.state());
+    /// ```
+    pub fn new(allies: Party, enemies: Party) -> Battle {
+        Battle { 
+            allies, 
+            enemies, 
+            rules: BattleRules::default(),
+            events: Vec::new(),
+            fled: None,
+            xp_awarded: false,
+            loot: None,
+        }
+    }
+
+    /// Borrows the party fighting on the given side.
+    pub fn party(&self, side: Side) -> &Party {
+        match side {
+            Side::Allies => &self.allies,
+            Side::Enemies => &self.enemies,
+        }
+    }
+
+    /// Mutably borrows the party fighting on the given side.
+    pub fn party_mut(&mut self, side: Side) -> &mut Party {
+        match side {
+            Side::Allies => &mut self.allies,
+            Side::Enemies => &mut self.enemies,
+        }
+    }
+
+    /// Borrows the combatant with the given id, if they exist.
+    pub fn combatant(&self, id: CombatantId) -> Option<&Combatant> {
+        self.party(id.side).members.get(id.index)
+    }
+
+    /// Returns the ids of every combatant on the given side who hasn't been 
+    /// defeated.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let allies = Party::new(vec![
+    ///     Combatant::new("Alice".to_string()),
+    ///     Combatant::new("Bob".to_string()),
+    /// ]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let mut battle = Battle::new(allies, enemies);
+    /// battle.allies.members[0].health.damage(10);
+    /// 
+    /// let living = battle.living(Side::Allies);
+    /// assert_eq!(vec![CombatantId { side: Side::Allies, index: 1 }], living);
+    /// ```
+    pub fn living(&self, side: Side) -> Vec<CombatantId> {
+        self.party(side).members.iter()
+            .enumerate()
+            .filter(|(_, member)| member.health.check_status() != HealthStatus::Defeated)
+            .map(|(index, _)| CombatantId { side, index })
+            .collect()
+    }
+
+    /// Returns the ids of every combatant the given combatant may attack: 
+    /// the living members of the opposing side.
+    pub fn valid_targets(&self, attacker: CombatantId) -> Vec<CombatantId> {
+        self.living(attacker.side.opponent())
+    }
+
+    /// Returns the ids of every combatant the given combatant may heal or use 
+    /// items on: the living members of their own side, including themself.
+    pub fn valid_support_targets(&self, actor: CombatantId) -> Vec<CombatantId> {
+        self.living(actor.side)
+    }
+
+    /// Returns whether the battle is still going, which side has won, or 
+    /// which side fled.
+    /// 
+    /// A side wins once every member of the opposing party is defeated.
+    pub fn state(&self) -> BattleState {
+        if let Some(side) = self.fled {
+            BattleState::Fled(side)
+        }
+        else if self.enemies.is_wiped() {
+            BattleState::Won(Side::Allies)
+        }
+        else if self.allies.is_wiped() {
+            BattleState::Won(Side::Enemies)
+        }
+        else {
+            BattleState::Ongoing
+        }
+    }
+
+    /// Has one combatant attack another, applying any damage dealt and 
+    /// recording what happened as [`BattleEvent`]s. Damage is rolled within 
+    /// the attacker's [`damage_variance`](Weapon::damage_variance). If the defender is 
+    /// defeated, the morale of their party drops. A hit that deals damage 
+    /// uses up the defender's [`guarding`](Combatant::guarding).
+    /// 
+    /// Returns the full [`AttackOutcome`]. 
+    /// 
+    /// # Errors
+    /// 
+    /// Returns a [`BattleError`] if the attacker can't act, if the battle is 
+    /// over, or if the target isn't one of their 
+    /// [`valid_targets`](Battle::valid_targets). 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::service::SeededRng;
+    /// use druid_game::weapon::Weapon;
+    /// 
+    /// let mut alice = Combatant::new("Alice".to_string());
+    /// alice.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+    /// let allies = Party::new(vec![alice]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let mut battle = Battle::new(allies, enemies);
+    /// 
+    /// let mut rng = SeededRng::new(42);
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// let vim = CombatantId { side: Side::Enemies, index: 0 };
+    /// 
+    /// let outcome = battle.attack(&mut rng, alice, vim).unwrap();
+    /// assert_eq!(10 - outcome.damage.unwrap(), battle.enemies.members[0].health.current());
+    /// 
+    /// // Alice can't attack herself.
+    /// assert!(battle.attack(&mut rng, alice, alice).is_err());
+    /// ```
+    pub fn attack(&mut self, rng: &mut dyn RngService, attacker: CombatantId, target: CombatantId) -> Result<AttackOutcome, BattleError> {
+        self.validate_attack(attacker, target)?;
+        let outcome = self.roll_weapon_attack(rng, false, attacker, target);
+        self.apply_attack(attacker, target, &outcome);
+        Ok(outcome)
+    }
+
+    /// Has a dual-wielding combatant make the extra attack with the weapon in 
+    /// their off hand, like [`attack`](Battle::attack). See 
+    /// [`resolve_off_hand_attack`].
+    /// 
+    /// # Errors
+    /// 
+    /// Returns a [`BattleError`] for the same reasons as 
+    /// [`attack`](Battle::attack).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::service::SeededRng;
+    /// use druid_game::weapon::{OffHand, Weapon};
+    /// 
+    /// let mut alice = Combatant::new("Alice".to_string());
+    /// alice.give_weapon(Weapon::new("Shortsword".to_string(), 70, 6));
+    /// alice.equip_off_hand(OffHand::Weapon(Weapon::new("Dagger".to_string(), 80, 4))).unwrap();
+    /// let allies = Party::new(vec![alice]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let mut battle = Battle::new(allies, enemies);
+    /// 
+    /// let mut rng = SeededRng::new(42);
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// let vim = CombatantId { side: Side::Enemies, index: 0 };
+    /// 
+    /// let outcome = battle.off_hand_attack(&mut rng, alice, vim).unwrap();
+    /// assert_eq!(Some(60), outcome.hit_rate);
+    /// ```
+    pub fn off_hand_attack(&mut self, rng: &mut dyn RngService, attacker: CombatantId, target: CombatantId) -> Result<AttackOutcome, BattleError> {
+        self.validate_attack(attacker, target)?;
+        let outcome = self.roll_weapon_attack(rng, true, attacker, target);
+        self.apply_attack(attacker, target, &outcome);
+        Ok(outcome)
+    }
+
+    /// Rolls to hit and for damage with the weapon in the attacker's main or 
+    /// off hand, then resolves the attack by the battle's rules.
+    fn roll_weapon_attack(&self, rng: &mut dyn RngService, off_hand: bool, attacker: CombatantId, target: CombatantId) -> AttackOutcome {
+        let attacker = &self.party(attacker.side).members[attacker.index];
+        let defender = &self.party(target.side).members[target.index];
+        let weapon = match off_hand {
+            false => attacker.current_weapon().as_ref(),
+            true => attacker.off_hand_weapon(),
+        };
+
+        let dice_roll = self.rules.roll(rng);
+        let damage_roll = weapon.map(|weapon| weapon.roll_damage(rng));
+        resolve_weapon_attack(&self.rules, off_hand, dice_roll, damage_roll, attacker, defender)
+    }
+
+    /// Checks that the attacker can attack the target.
+    fn validate_attack(&self, attacker: CombatantId, target: CombatantId) -> Result<(), BattleError> {
+        if !self.living(attacker.side).contains(&attacker) {
+            return Err(BattleError::InvalidActor(attacker));
+        }
+        if self.state() != BattleState::Ongoing {
+            return Err(BattleError::BattleOver);
+        }
+        if !self.valid_targets(attacker).contains(&target) {
+            return Err(BattleError::InvalidTarget(target));
+        }
+        Ok(())
+    }
+
+    /// Records a resolved attack and applies its damage.
+    fn apply_attack(&mut self, attacker: CombatantId, target: CombatantId, outcome: &AttackOutcome) {
+        self.events.push(BattleEvent::AttackDeclared { attacker, target });
+        self.events.push(BattleEvent::AttackResolved { 
+            attacker, 
+            target, 
+            result: outcome.result,
+        });
+        if let Some(damage) = outcome.damage {
+            self.deal_damage(target, damage);
+        }
+    }
+
+    /// Damages a combatant and records what happened, using up their 
+    /// [`guarding`](Combatant::guarding). If they are defeated, the morale of 
+    /// their party drops. All incoming damage goes through here.
+    fn deal_damage(&mut self, target: CombatantId, damage: i32) {
+        let defending_party = self.party_mut(target.side);
+        let defender = &mut defending_party.members[target.index];
+        defender.guarding = false;
+        let report = defender.health.apply_damage(damage);
+        let remaining = defender.health.current();
+        self.events.push(BattleEvent::Damaged { target, damage, remaining });
+
+        if report.status == HealthStatus::Defeated {
+            self.party_mut(target.side).on_member_defeated();
+            self.events.push(BattleEvent::Defeated { target, overkill: report.overkill });
+
+            if let BattleState::Won(winner) = self.state() {
+                self.events.push(BattleEvent::BattleEnded { winner });
+            }
+        }
+    }
+
+    /// Has a combatant take an action on their turn, recording what happened 
+    /// as [`BattleEvent`]s. The action's [`cost`](BattleAction::cost) is 
+    /// spent from the combatant's action points, which are restored at the 
+    /// [start of each round](Battle::start_round). 
+    /// 
+    /// # Errors
+    /// 
+    /// Returns a [`BattleError`] if the actor can't act, if the target isn't 
+    /// valid for the action, if the item to use doesn't exist or has no 
+    /// effect, or if the actor can't afford the action's cost. Nothing 
+    /// happens if the action is rejected. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, BattleAction, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::inventory::{Item, ItemEffect};
+    /// use druid_game::service::SeededRng;
+    /// 
+    /// let mut alice = Combatant::new("Alice".to_string());
+    /// alice.health.damage(6);
+    /// alice.inventory.add_item(Item::with_effect("Herb".to_string(), ItemEffect::Heal(3))).unwrap();
+    /// let allies = Party::new(vec![alice]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let mut battle = Battle::new(allies, enemies);
+    /// 
+    /// let mut rng = SeededRng::new(42);
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// 
+    /// battle.resolve_action(&mut rng, alice, BattleAction::UseItem { item: 0, target: alice }).unwrap();
+    /// assert_eq!(7, battle.allies.members[0].health.current());
+    /// assert!(battle.allies.members[0].inventory.items().is_empty());
+    /// 
+    /// battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+    /// assert!(battle.allies.members[0].guarding);
+    /// ```
+    pub fn resolve_action(&mut self, rng: &mut dyn RngService, actor: CombatantId, action: BattleAction) -> Result<(), BattleError> {
+        self.validate_action(actor, action)?;
+        let combatant = &mut self.party_mut(actor.side).members[actor.index];
+        combatant.spend_action_points(action.cost())
+            .expect("Action points should be sufficient after validation");
+
+        match action {
+            BattleAction::Attack { target } => {
+                self.attack(rng, actor, target)?;
+                let dual_wielding = self.party(actor.side).members[actor.index].off_hand_weapon().is_some();
+                if dual_wielding && self.valid_targets(actor).contains(&target) {
+                    self.off_hand_attack(rng, actor, target)?;
+                }
+            },
+            BattleAction::Heal { target } => self.restore_health(target, HEAL_AMOUNT),
+            BattleAction::Cast { spell, target } => self.cast(rng, actor, spell, target),
+            BattleAction::Escape => self.escape(rng, actor),
+            BattleAction::Defend => {
+                self.party_mut(actor.side).members[actor.index].guarding = true;
+                self.events.push(BattleEvent::Defending { target: actor });
+            },
+            BattleAction::UseItem { item, target } => {
+                let item = self.party_mut(actor.side).members[actor.index].inventory
+                    .remove_item(item)
+                    .expect("Item should exist after validation");
+                self.events.push(BattleEvent::ItemUsed { user: actor, item: item.name, target });
+                match item.effect {
+                    Some(ItemEffect::Heal(amount)) => self.restore_health(target, amount),
+                    None => unreachable!("Item should have an effect after validation"),
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Checks that an action can be taken, without changing anything.
+    fn validate_action(&self, actor: CombatantId, action: BattleAction) -> Result<(), BattleError> {
+        if !self.living(actor.side).contains(&actor) {
+            return Err(BattleError::InvalidActor(actor));
+        }
+        if self.state() != BattleState::Ongoing {
+            return Err(BattleError::BattleOver);
+        }
+        match action {
+            BattleAction::Attack { target } => {
+                if !self.valid_targets(actor).contains(&target) {
+                    return Err(BattleError::InvalidTarget(target));
+                }
+            },
+            BattleAction::Heal { target } => {
+                if !self.valid_support_targets(actor).contains(&target) {
+                    return Err(BattleError::InvalidTarget(target));
+                }
+            },
+            BattleAction::Defend | BattleAction::Escape => {},
+            BattleAction::Cast { spell, target } => {
+                let caster = &self.party(actor.side).members[actor.index];
+                let spell = caster.spells.get(spell)
+                    .ok_or(BattleError::InvalidSpell(spell))?;
+                if spell.mana_cost > caster.mana() {
+                    return Err(BattleError::NotEnoughMana(NotEnoughMana { 
+                        cost: spell.mana_cost, 
+                        available: caster.mana(),
+                    }));
+                }
+                let valid_targets = match spell.effect {
+                    SpellEffect::Damage => self.valid_targets(actor),
+                    SpellEffect::Heal => self.valid_support_targets(actor),
+                };
+                if !valid_targets.contains(&target) {
+                    return Err(BattleError::InvalidTarget(target));
+                }
+            },
+            BattleAction::UseItem { item, target } => {
+                let usable = self.party(actor.side).members[actor.index].inventory.items()
+                    .get(item)
+                    .is_some_and(|item| item.effect.is_some());
+                if !usable {
+                    return Err(BattleError::InvalidItem(item));
+                }
+                if !self.valid_support_targets(actor).contains(&target) {
+                    return Err(BattleError::InvalidTarget(target));
+                }
+            },
+        }
+
+        let available = self.party(actor.side).members[actor.index].action_points();
+        if action.cost() > available {
+            return Err(BattleError::NotEnoughActionPoints(NotEnoughActionPoints { 
+                cost: action.cost(), 
+                available,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Casts a spell, spending its mana and applying its effect. The spell 
+    /// must already have been validated.
+    fn cast(&mut self, rng: &mut dyn RngService, actor: CombatantId, spell: usize, target: CombatantId) {
+        let caster = &mut self.party_mut(actor.side).members[actor.index];
+        let spell = caster.spells[spell].clone();
+        caster.spend_mana(spell.mana_cost)
+            .expect("Mana should be sufficient after validation");
+
+        let caster = &self.party(actor.side).members[actor.index];
+        let defender = &self.party(target.side).members[target.index];
+        let dice_roll = self.rules.roll(rng);
+        let result = resolve_spell_with_rules(&self.rules, dice_roll, &spell, caster, defender);
+        let power = calculate_spell_power_with_rules(&self.rules, &result, &spell, defender);
+
+        self.events.push(BattleEvent::SpellCast { caster: actor, spell: spell.name.clone(), target });
+        match (spell.effect, power) {
+            (SpellEffect::Damage, power) => {
+                self.events.push(BattleEvent::AttackResolved { attacker: actor, target, result });
+                if let Some(power) = power {
+                    self.deal_damage(target, power);
+                }
+            },
+            (SpellEffect::Heal, Some(power)) => self.restore_health(target, power),
+            (SpellEffect::Heal, None) => unreachable!("Healing spells should never miss"),
+        }
+    }
+
+    /// Returns the chance, out of 100, of the given combatant leading their 
+    /// party's escape, as given by [`calculate_escape_chance`] against the 
+    /// fastest living opponent.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let mut vim = Combatant::new("Vim".to_string());
+    /// vim.stats.speed = 2;
+    /// let enemies = Party::new(vec![vim, Combatant::new("Nano".to_string())]);
+    /// let battle = Battle::new(allies, enemies);
+    /// 
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// assert_eq!(30, battle.escape_chance(alice));
+    /// ```
+    pub fn escape_chance(&self, actor: CombatantId) -> i32 {
+        let Some(runner) = self.combatant(actor) else {
+            return 0;
+        };
+        self.valid_targets(actor).into_iter()
+            .filter_map(|id| self.combatant(id))
+            .max_by_key(|pursuer| pursuer.effective_stats().speed)
+            .map_or(100, |pursuer| calculate_escape_chance(runner, pursuer))
+    }
+
+    /// Rolls a hundred-sided die for the actor's party to escape, ending the 
+    /// battle on a success. Escape chances are percentages, so this doesn't 
+    /// use the die from the battle's rules.
+    fn escape(&mut self, rng: &mut dyn RngService, actor: CombatantId) {
+        if rng.roll_d100() <= self.escape_chance(actor) {
+            self.fled = Some(actor.side);
+            self.events.push(BattleEvent::Fled { actor });
+        }
+        else {
+            self.events.push(BattleEvent::EscapeFailed { actor });
+        }
+    }
+
+    /// Heals a combatant and records how much health they regained.
+    fn restore_health(&mut self, target: CombatantId, amount: i32) {
+        let health = &mut self.party_mut(target.side).members[target.index].health;
+        let before = health.current();
+        health.heal(amount);
+        let remaining = health.current();
+        self.events.push(BattleEvent::Healed { 
+            target, 
+            amount: remaining - before, 
+            remaining,
+        });
+    }
+
+    /// Once the battle has been won, grants every surviving member of the 
+    /// winning side the combined [`xp_reward`] of the losing side, leveling 
+    /// them up according to the given table. Experience is only awarded 
+    /// once per battle.
+    /// 
+    /// Returns the experience granted to each survivor.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::progression::XpTable;
+    /// 
+    /// let mut vim = Combatant::new("Vim".to_string());
+    /// vim.xp_reward = 15;
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let mut battle = Battle::new(allies, Party::new(vec![vim]));
+    /// battle.enemies.members[0].health.damage(10);
+    /// 
+    /// let table = XpTable::new(vec![10, 30]);
+    /// assert_eq!(15, battle.award_xp(&table));
+    /// assert_eq!(2, battle.allies.members[0].level());
+    /// 
+    /// // Experience can't be awarded twice.
+    /// assert_eq!(0, battle.award_xp(&table));
+    /// ```
+    /// 
+    /// [`xp_reward`]: Combatant::xp_reward
+    pub fn award_xp(&mut self, table: &XpTable) -> u32 {
+        let BattleState::Won(winner) = self.state() else {
+            return 0;
+        };
+        if self.xp_awarded {
+            return 0;
+        }
+        self.xp_awarded = true;
+
+        let amount = self.party(winner.opponent()).members.iter()
+            .map(|member| member.xp_reward)
+            .sum();
+        for target in self.living(winner) {
+            let member = &mut self.party_mut(winner).members[target.index];
+            let levels_gained = member.award_xp(amount, table);
+            let level = member.level();
+
+            self.events.push(BattleEvent::ExperienceGained { target, amount });
+            if levels_gained > 0 {
+                self.events.push(BattleEvent::LevelUp { target, level });
+            }
+        }
+        amount
+    }
+
+    /// Once the allies have won, rolls each enemy's [`drop_table`] once and 
+    /// collects what they drop. Loot is only rolled once per battle, and 
+    /// nothing is dropped if the enemies win.
+    /// 
+    /// Returns the collected loot, which stays available from 
+    /// [`loot`](Battle::loot) afterwards.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::Battle;
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::loot::{DropTable, Loot};
+    /// use druid_game::service::SeededRng;
+    /// 
+    /// let mut vim = Combatant::new("Vim".to_string());
+    /// vim.drop_table = DropTable::new(vec![(1, Loot::Gold(12))]);
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let mut battle = Battle::new(allies, Party::new(vec![vim]));
+    /// let mut rng = SeededRng::new(42);
+    /// 
+    /// // The battle isn't over yet.
+    /// assert!(battle.roll_loot(&mut rng).is_empty());
+    /// 
+    /// battle.enemies.members[0].health.damage(10);
+    /// assert_eq!(12, battle.roll_loot(&mut rng).gold);
+    /// assert_eq!(12, battle.loot().unwrap().gold);
+    /// ```
+    /// 
+    /// [`drop_table`]: Combatant::drop_table
+    pub fn roll_loot(&mut self, rng: &mut dyn RngService) -> LootBundle {
+        if self.state() != BattleState::Won(Side::Allies) {
+            return LootBundle::default();
+        }
+        if let Some(loot) = &self.loot {
+            return loot.clone();
+        }
+
+        let mut loot = LootBundle::default();
+        for enemy in &self.enemies.members {
+            if let Some(drop) = enemy.drop_table.roll(rng) {
+                loot.add(drop.clone());
+            }
+        }
+        self.loot = Some(loot.clone());
+        loot
+    }
+
+    /// Borrows the loot collected by [`roll_loot`](Battle::roll_loot), if it 
+    /// has been rolled.
+    pub fn loot(&self) -> Option<&LootBundle> {
+        self.loot.as_ref()
+    }
+
+    /// Removes and returns every event recorded since the last call.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, BattleEvent, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::service::SeededRng;
+    /// 
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let mut battle = Battle::new(allies, enemies);
+    /// 
+    /// let mut rng = SeededRng::new(42);
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// let vim = CombatantId { side: Side::Enemies, index: 0 };
+    /// battle.attack(&mut rng, alice, vim).unwrap();
+    /// 
+    /// let events = battle.take_events();
+    /// assert_eq!(BattleEvent::AttackDeclared { attacker: alice, target: vim }, events[0]);
+    /// assert!(battle.take_events().is_empty());
+    /// ```
+    pub fn take_events(&mut self) -> Vec<BattleEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Describes an event in a line or two of text, using the names of the 
+    /// combatants involved.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, BattleEvent, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let battle = Battle::new(allies, enemies);
+    /// 
+    /// let event = BattleEvent::AttackDeclared {
+    ///     attacker: CombatantId { side: Side::Allies, index: 0 },
+    ///     target: CombatantId { side: Side::Enemies, index: 0 },
+    /// };
+    /// assert_eq!("Alice attacks Vim", battle.describe(&event));
+    /// ```
+    pub fn describe(&self, event: &BattleEvent) -> String {
+        let name = |id: &CombatantId| {
+            self.combatant(*id)
+                .map(|combatant| combatant.name.as_str())
+                .unwrap_or("Someone")
+        };
+
+        match event {
+            BattleEvent::AttackDeclared { attacker, target } => {
+                format!("{0} attacks {1}", name(attacker), name(target))
+            },
+            BattleEvent::AttackResolved { attacker, result, .. } => match result {
+                AttackResult::Miss => format!("{0} missed!", name(attacker)),
+                AttackResult::NoWeapon => format!("{0} didn't equip a weapon!", name(attacker)),
+                AttackResult::CriticalHit => "A critical hit!".to_string(),
+                AttackResult::DirectHit => "It's a direct hit!".to_string(),
+                AttackResult::Graze => "It's a graze.".to_string(),
+                AttackResult::GlancingBlow => "It's a glancing blow.".to_string(),
+            },
+            BattleEvent::Damaged { target, damage, remaining } => {
+                format!("{0} takes {damage} damage.\n{0} has {remaining} hit points remaining.", 
+                    name(target))
+            },
+            BattleEvent::SpellCast { caster, spell, target } => {
+                format!("{0} casts {spell} on {1}", name(caster), name(target))
+            },
+            BattleEvent::Healed { target, amount, remaining } => {
+                format!("{0} recovers {amount} hit points.\n{0} has {remaining} hit points.", 
+                    name(target))
+            },
+            BattleEvent::Fled { actor: CombatantId { side: Side::Allies, .. } } => {
+                "The allies got away safely.".to_string()
+            },
+            BattleEvent::Fled { actor: CombatantId { side: Side::Enemies, .. } } => {
+                "The enemies fled!".to_string()
+            },
+            BattleEvent::EscapeFailed { actor } => format!("{0} couldn't get away!", name(actor)),
+            BattleEvent::Defending { target } => format!("{0} braces for attacks.", name(target)),
+            BattleEvent::ItemUsed { user, item, target } if user == target => {
+                format!("{0} uses {item}.", name(user))
+            },
+            BattleEvent::ItemUsed { user, item, target } => {
+                format!("{0} uses {item} on {1}.", name(user), name(target))
+            },
+            BattleEvent::ModifierExpired { target, modifier } => {
+                format!("{0}'s {1} wore off.", name(target), modifier.name)
+            },
+            BattleEvent::Defeated { target, overkill } => {
+                let finish = match overkill {
+                    0 => "An exact kill!".to_string(),
+                    _ => format!("Overkill by {overkill}."),
+                };
+                format!("{0} is defeated!\n{finish}", name(target))
+            },
+            BattleEvent::BattleEnded { winner: Side::Allies } => "The allies are victorious!".to_string(),
+            BattleEvent::BattleEnded { winner: Side::Enemies } => "The allies have been defeated...".to_string(),
+            BattleEvent::ExperienceGained { target, amount } => {
+                format!("{0} gains {amount} experience.", name(target))
+            },
+            BattleEvent::LevelUp { target, level } => {
+                format!("{0} reached level {level}!", name(target))
+            },
+        }
+    }
+
+    /// Rolls initiative for every living combatant, returning their ids in 
+    /// the order they act this round. Combatants with higher effective speed 
+    /// act first, and ties are broken by a roll of the dice. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::service::SeededRng;
+    /// 
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let mut vim = Combatant::new("Vim".to_string());
+    /// vim.stats.speed = 3;
+    /// let battle = Battle::new(allies, Party::new(vec![vim]));
+    /// 
+    /// let mut rng = SeededRng::new(42);
+    /// assert_eq!(vec![
+    ///     CombatantId { side: Side::Enemies, index: 0 },
+    ///     CombatantId { side: Side::Allies, index: 0 },
+    /// ], battle.turn_order(&mut rng));
+    /// ```
+    pub fn turn_order(&self, rng: &mut dyn RngService) -> Vec<CombatantId> {
+        let mut initiative: Vec<(CombatantId, i32, i32)> = [Side::Allies, Side::Enemies].into_iter()
+            .flat_map(|side| self.living(side))
+            .filter_map(|id| {
+                let speed = self.combatant(id)?.effective_stats().speed;
+                Some((id, speed, rng.roll_d100()))
+            })
+            .collect();
+        initiative.sort_by(|(_, speed_a, roll_a), (_, speed_b, roll_b)| {
+            speed_b.cmp(speed_a).then(roll_b.cmp(roll_a))
+        });
+        initiative.into_iter().map(|(id, _, _)| id).collect()
+    }
+
+    /// Starts a new round, restoring every combatant's action points to 
+    /// their maximum. Game loops that don't use 
+    /// [`run_round`](Battle::run_round) should call this before anyone acts 
+    /// in the round.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, BattleAction, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::service::SeededRng;
+    /// 
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let mut battle = Battle::new(allies, enemies);
+    /// let mut rng = SeededRng::new(42);
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// 
+    /// battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+    /// battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+    /// assert!(battle.resolve_action(&mut rng, alice, BattleAction::Defend).is_err());
+    /// 
+    /// battle.start_round();
+    /// assert!(battle.resolve_action(&mut rng, alice, BattleAction::Defend).is_ok());
+    /// ```
+    pub fn start_round(&mut self) {
+        for party in [&mut self.allies, &mut self.enemies] {
+            for member in &mut party.members {
+                member.refresh_action_points();
+            }
+        }
+    }
+
+    /// Ends the current round, counting down the buffs and debuffs of every 
+    /// living combatant and recording those that run out. Game loops that 
+    /// don't use [`run_round`](Battle::run_round) should call this once all 
+    /// combatants have acted.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, BattleEvent, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::{Combatant, CombatStats, StatModifier};
+    /// 
+    /// let mut alice = Combatant::new("Alice".to_string());
+    /// let haste = CombatStats { speed: 5, ..CombatStats::new() };
+    /// alice.modifiers.push(StatModifier::new("Haste".to_string(), haste, 1));
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let mut battle = Battle::new(Party::new(vec![alice]), enemies);
+    /// 
+    /// battle.end_round();
+    /// assert!(battle.allies.members[0].modifiers.is_empty());
+    /// assert!(matches!(battle.take_events()[0], BattleEvent::ModifierExpired { .. }));
+    /// ```
+    pub fn end_round(&mut self) {
+        for side in [Side::Allies, Side::Enemies] {
+            for target in self.living(side) {
+                let expired = self.party_mut(side).members[target.index].tick_modifiers();
+                for modifier in expired {
+                    self.events.push(BattleEvent::ModifierExpired { target, modifier });
+                }
+            }
+        }
+    }
+
+    /// Counts one tick of every defeated combatant's defeat animation with 
+    /// [`Combatant::tick_defeat`], returning those whose 
+    /// [`defeat_timer`](Combatant::defeat_timer) ran out on this tick so the 
+    /// frontend can stop drawing them. 
+    /// 
+    /// Defeated combatants stay in their party, so every [`CombatantId`] 
+    /// stays valid for the rest of the battle. Use 
+    /// [`Party::remove_defeated`](crate::combat::Party::remove_defeated) to 
+    /// clear them out once the battle is over.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, CombatantId, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let mut vim = Combatant::new("Vim".to_string());
+    /// vim.defeat_timer = 1;
+    /// vim.health.damage(10);
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let mut battle = Battle::new(allies, Party::new(vec![vim, Combatant::new("Nano".to_string())]));
+    /// 
+    /// let vim = CombatantId { side: Side::Enemies, index: 0 };
+    /// assert!(battle.tick_defeated().is_empty());
+    /// assert_eq!(vec![vim], battle.tick_defeated());
+    /// assert_eq!(2, battle.enemies.members.len());
+    /// ```
+    pub fn tick_defeated(&mut self) -> Vec<CombatantId> {
+        let mut finished = Vec::new();
+        for side in [Side::Allies, Side::Enemies] {
+            for (index, member) in self.party_mut(side).members.iter_mut().enumerate() {
+                if member.tick_defeat() {
+                    finished.push(CombatantId { side, index });
+                }
+            }
+        }
+        finished
+    }
+
+    /// Plays out one round of the battle. The round is 
+    /// [started](Battle::start_round), then every living combatant takes a 
+    /// [`BattleAction::Attack`] against the first of their valid targets, in 
+    /// the [`turn_order`](Battle::turn_order) rolled at the start of the 
+    /// round, then the round is [ended](Battle::end_round). Once either side 
+    /// is defeated, the remaining turns are skipped, but the round still 
+    /// ends.
+    /// 
+    /// Attacks go through [`resolve_action`](Battle::resolve_action), so 
+    /// they spend action points and dual-wielders follow up with their off 
+    /// hand. Combatants who can't afford an attack skip their turn.
+    /// 
+    /// Returns the state of the battle at the end of the round.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::battle::{Battle, BattleState, Side};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::service::SeededRng;
+    /// use druid_game::weapon::Weapon;
+    /// 
+    /// let mut alice = Combatant::new("Alice".to_string());
+    /// alice.give_weapon(Weapon::new("Greatsword".to_string(), 100, 20));
+    /// let allies = Party::new(vec![alice]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let mut battle = Battle::new(allies, enemies);
+    /// 
+    /// let mut rng = SeededRng::new(42);
+    /// assert_eq!(BattleState::Won(Side::Allies), battle.run_round(&mut rng));
+    /// ```
+    pub fn run_round(&mut self, rng: &mut dyn RngService) -> BattleState {
+        self.start_round();
+        for attacker in self.turn_order(rng) {
+            let Some(&target) = self.valid_targets(attacker).first() else {
+                break;
+            };
+            // Combatants defeated earlier in the round, or short on action 
+            // points, can't act, so their attacks are rejected and skipped. 
+            let _ = self.resolve_action(rng, attacker, BattleAction::Attack { target });
+        }
+        self.end_round();
+        self.state()
+    }
+}
+
+/// Asserts that the battle formulas treat two mirror-image combatants the 
+/// same way, no matter which of them is attacking.
+/// 
+/// The two combatants must have identical stats, effective stats, weapons, 
+/// morale, modifiers, elements and rows, and must both be guarding or not. 
+/// Their attack results and damage are compared for 
+/// every roll from 1 through 100, with each of them taking a turn as the 
+/// attacker. 
+/// 
+/// # Panics
+/// 
+/// Panics if the combatants are not mirror images of each other, or if 
+/// swapping the attacker and defender changes the outcome of any roll. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use druid_game::battle;
+/// use druid_game::combatant::Combatant;
+/// use druid_game::weapon::Weapon;
+/// 
+/// let mut alice = Combatant::new("Alice".to_string());
+/// alice.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+/// alice.stats.accuracy = 5;
+/// let mut vim = Combatant::new("Vim".to_string());
+/// vim.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+/// vim.stats.accuracy = 5;
+/// 
+/// battle::assert_symmetric(&alice, &vim);
+/// ```
+#[cfg(feature = "test-util")]
+pub fn assert_symmetric(a: &Combatant, b: &Combatant) {
+    assert_symmetric_by(a, b, |attacker, defender| {
+        (1..=100)
+            .map(|dice_roll| {
+                let attack_result = resolve_attack(dice_roll, attacker, defender);
+                let damage = calculate_damage(&attack_result, attacker, defender);
+                (attack_result, damage)
+            })
+            .collect::<Vec<_>>()
+    });
+}
+
+/// Asserts that the given formula treats two mirror-image combatants the 
+/// same way, no matter which of them is attacking. 
+/// 
+/// This works like [`assert_symmetric`], but checks an arbitrary formula 
+/// instead of the built-in attack resolution. 
+/// 
+/// # Panics
+/// 
+/// Panics if the combatants are not mirror images of each other, or if 
+/// swapping the attacker and defender changes the formula's result.
+#[cfg(feature = "test-util")]
+pub fn assert_symmetric_by<T, F>(a: &Combatant, b: &Combatant, formula: F) 
+where
+    T: PartialEq + std::fmt::Debug,
+    F: Fn(&Combatant, &Combatant) -> T,
+{
+    assert_eq!(a.stats, b.stats, 
+        "{a} and {b} must have the same stats to be mirror images.");
+    assert_eq!(a.current_weapon(), b.current_weapon(),
+        "{a} and {b} must have the same weapon to be mirror images.");
+    assert_eq!(a.off_hand(), b.off_hand(),
+        "{a} and {b} must hold the same thing in their off hands to be mirror images.");
+    assert_eq!(a.morale, b.morale,
+        "{a} and {b} must have the same morale to be mirror images.");
+    assert_eq!(a.modifiers, b.modifiers,
+        "{a} and {b} must have the same modifiers to be mirror images.");
+    assert_eq!(a.element, b.element,
+        "{a} and {b} must have the same element to be mirror images.");
+    assert_eq!(a.row, b.row,
+        "{a} and {b} must stand in the same row to be mirror images.");
+    assert_eq!(a.effective_stats(), b.effective_stats(),
+        "{a} and {b} must have the same effective stats to be mirror images.");
+    assert_eq!(a.guarding, b.guarding,
+        "{a} and {b} must both be guarding or not to be mirror images.");
+
+    let forward = formula(a, b);
+    let reverse = formula(b, a);
+    assert_eq!(forward, reverse, 
+        "Swapping {a} and {b} must not change the outcome.");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::combat::Row;
+    use crate::combatant::CombatStats;
+    use crate::element::Element;
+    use crate::inventory::Item;
+    use crate::weapon::OffHand;
+    use crate::loot::{DropTable, Loot};
+    use crate::service::SeededRng;
+    use crate::weapon::Weapon;
+
+    #[test]
+    fn test_analyze_attack() {
+        let mut weapon = Weapon::new("Keen Sword".to_string(), 60, 10);
+        weapon.stat_bonus.accuracy = 5;
+        let mut attacker = Combatant::new("Alice".to_string());
+        attacker.give_weapon(weapon);
+        attacker.stats.strength = 3;
+        attacker.morale = -2;
+        let mut defender = Combatant::new("Vim".to_string());
+        defender.stats.evasion = 15;
+        defender.stats.defense = 1;
+
+        let expected = AttackAnalysis {
+            weapon_hit_rate: 60,
+            accuracy_bonus: 5,
+            morale_bonus: -2,
+            evasion_penalty: 15,
+            range_penalty: 0,
+            hit_rate: 48,
+            weapon_damage: 10,
+            strength_bonus: 3,
+            defense_reduction: 1,
+            base_damage: 12,
+            effectiveness: Effectiveness::Neutral,
+            guard_multiplier: 1.0,
+            critical_hit_damage: 24,
+            direct_hit_damage: 12,
+            graze_damage: 9,
+            glancing_blow_damage: 6,
+        };
+        assert_eq!(Some(expected), analyze_attack(&BattleRules::default(), &attacker, &defender),
+            "Each component of the analysis must match the inputs.");
+    }
+
+    #[test]
+    fn test_range_penalty() {
+        let mut bow = Weapon::new("Bow".to_string(), 60, 6);
+        bow.reach = 3;
+        let mut archer = Combatant::new("Alice".to_string());
+        archer.give_weapon(bow);
+        archer.row = Row::Back;
+        let mut defender = Combatant::new("Vim".to_string());
+        defender.row = Row::Back;
+
+        assert_eq!(Some(60), calculate_hit_rate(&archer, &defender),
+            "Defenders within reach must not be harder to hit.");
+
+        archer.give_weapon(Weapon::new("Dagger".to_string(), 60, 4));
+        let analysis = analyze_attack(&BattleRules::default(), &archer, &defender).unwrap();
+        assert_eq!(40, analysis.range_penalty,
+            "Each step beyond reach must add to the penalty.");
+        assert_eq!(20, analysis.hit_rate);
+    }
+
+    #[test]
+    fn test_analysis_matches_battle_damage() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.rules = BattleRules { crit_multiplier: 3.0, min_damage: Some(1), ..Default::default() };
+        battle.enemies.members[0].guarding = true;
+        battle.enemies.members[0].stats.defense = 2;
+
+        let analysis = analyze_attack(&battle.rules, &battle.allies.members[0], &battle.enemies.members[0]).unwrap();
+        assert_eq!(GUARD_MULTIPLIER, analysis.guard_multiplier);
+        let outcome = battle.attack(&mut rng, alice, vim).unwrap();
+        let expected = match outcome.result {
+            AttackResult::CriticalHit => analysis.critical_hit_damage,
+            AttackResult::DirectHit => analysis.direct_hit_damage,
+            AttackResult::Graze => analysis.graze_damage,
+            AttackResult::GlancingBlow => analysis.glancing_blow_damage,
+            AttackResult::Miss | AttackResult::NoWeapon => unreachable!("Alice must be able to hit"),
+        };
+        let dealt = battle.take_events().into_iter()
+            .find_map(|event| match event {
+                BattleEvent::Damaged { damage, .. } => Some(damage),
+                _ => None,
+            });
+        assert_eq!(Some(expected), dealt,
+            "The analyzed damage must be the damage the battle deals.");
+    }
+
+    #[test]
+    fn test_analyze_attack_without_weapon() {
+        let attacker = Combatant::new("Alice".to_string());
+        let defender = Combatant::new("Vim".to_string());
+
+        assert_eq!(None, analyze_attack(&BattleRules::default(), &attacker, &defender));
+    }
+
+    fn test_battle() -> Battle {
+        let mut alice = Combatant::new("Alice".to_string());
+        alice.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+        let mut bob = Combatant::new("Bob".to_string());
+        bob.give_weapon(Weapon::new("Club".to_string(), 60, 6));
+        let mut vim = Combatant::new("Vim".to_string());
+        vim.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+
+        Battle::new(Party::new(vec![alice, bob]), Party::new(vec![vim]))
+    }
+
+    #[test]
+    fn test_valid_targets_exclude_defeated_and_allies() {
+        let mut battle = test_battle();
+        battle.enemies.members.push(Combatant::new("Goblin".to_string()));
+        battle.enemies.members[0].health.damage(10);
+
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        assert_eq!(vec![CombatantId { side: Side::Enemies, index: 1 }],
+            battle.valid_targets(alice),
+            "Only living opponents may be targeted.");
+    }
+
+    #[test]
+    fn test_attack_rejects_invalid_ids() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(0);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let bob = CombatantId { side: Side::Allies, index: 1 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        let nobody = CombatantId { side: Side::Enemies, index: 5 };
+
+        assert_eq!(Err(BattleError::InvalidTarget(bob)), battle.attack(&mut rng, alice, bob));
+        assert_eq!(Err(BattleError::InvalidTarget(nobody)), battle.attack(&mut rng, alice, nobody));
+
+        battle.allies.members[0].health.damage(10);
+        assert_eq!(Err(BattleError::InvalidActor(alice)), battle.attack(&mut rng, alice, vim),
+            "Defeated combatants must not be able to attack.");
+    }
+
+    #[test]
+    fn test_battle_runs_until_a_side_is_defeated() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1234);
+
+        let mut state = BattleState::Ongoing;
+        for _ in 0..100 {
+            state = battle.run_round(&mut rng);
+            if state != BattleState::Ongoing {
+                break;
+            }
+        }
+
+        match state {
+            BattleState::Won(Side::Allies) => assert!(battle.enemies.is_wiped()),
+            BattleState::Won(Side::Enemies) => assert!(battle.allies.is_wiped()),
+            BattleState::Fled(_) => panic!("Nobody tries to escape in a plain round."),
+            BattleState::Ongoing => panic!("Battle must end once a side is defeated."),
+        }
+    }
+
+    #[test]
+    fn test_attack_events() {
+        let mut battle = test_battle();
+        battle.enemies.members[0].health.damage(9);
+        // Strong enough to defeat Vim even with a glancing blow.
+        battle.allies.members[0].stats.strength = 10;
+        let mut rng = SeededRng::new(7);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+
+        let outcome = battle.attack(&mut rng, alice, vim).unwrap();
+        let result = outcome.result;
+        let damage = outcome.damage.unwrap();
+        assert_eq!(HealthStatus::Defeated, outcome.defender_status);
+
+        let expected = vec![
+            BattleEvent::AttackDeclared { attacker: alice, target: vim },
+            BattleEvent::AttackResolved { attacker: alice, target: vim, result },
+            BattleEvent::Damaged { target: vim, damage, remaining: 0 },
+            BattleEvent::Defeated { target: vim, overkill: damage - 1 },
+            BattleEvent::BattleEnded { winner: Side::Allies },
+        ];
+        assert_eq!(expected, battle.take_events(),
+            "A defeating attack must record each step in order.");
+    }
+
+    #[test]
+    fn test_turn_order() {
+        let mut battle = test_battle();
+        battle.allies.members[1].stats.speed = 2;
+        battle.enemies.members[0].stats.speed = 1;
+        let mut rng = SeededRng::new(1);
+
+        let order = battle.turn_order(&mut rng);
+        assert_eq!(vec![
+            CombatantId { side: Side::Allies, index: 1 },
+            CombatantId { side: Side::Enemies, index: 0 },
+            CombatantId { side: Side::Allies, index: 0 },
+        ], order, "Faster combatants must act first.");
+
+        battle.allies.members[1].health.damage(10);
+        assert_eq!(2, battle.turn_order(&mut rng).len(),
+            "Defeated combatants must not take a turn.");
+    }
+
+    #[test]
+    fn test_turn_order_ties() {
+        let battle = test_battle();
+        let mut first = SeededRng::new(5);
+        let mut second = SeededRng::new(5);
+
+        assert_eq!(battle.turn_order(&mut first), battle.turn_order(&mut second),
+            "Ties must be broken the same way for the same seed.");
+        let orders: Vec<_> = (0..50).map(|_| battle.turn_order(&mut first)[0]).collect();
+        assert!(orders.iter().any(|&id| id != orders[0]),
+            "Ties must not always be broken in the same combatant's favor.");
+    }
+
+    #[test]
+    fn test_modifiers_affect_hit_rate() {
+        let mut battle = test_battle();
+        let curse = CombatStats { accuracy: -20, ..CombatStats::new() };
+        battle.allies.members[0].modifiers.push(StatModifier::new("Curse".to_string(), curse, 1));
+        let alice = &battle.allies.members[0];
+        let vim = &battle.enemies.members[0];
+
+        assert_eq!(Some(50), calculate_hit_rate(alice, vim),
+            "Debuffs must lower the hit rate.");
+
+        battle.end_round();
+        let alice = &battle.allies.members[0];
+        let vim = &battle.enemies.members[0];
+        assert_eq!(Some(70), calculate_hit_rate(alice, vim),
+            "Expired debuffs must no longer apply.");
+    }
+
+    #[test]
+    fn test_heal_action() {
+        let mut battle = test_battle();
+        battle.allies.members[1].health.damage(8);
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let bob = CombatantId { side: Side::Allies, index: 1 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+
+        assert_eq!(Err(BattleError::InvalidTarget(vim)),
+            battle.resolve_action(&mut rng, alice, BattleAction::Heal { target: vim }),
+            "Opponents must not be healed.");
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Heal { target: bob }).unwrap();
+        battle.resolve_action(&mut rng, alice, BattleAction::Heal { target: bob }).unwrap();
+        assert_eq!(vec![
+            BattleEvent::Healed { target: bob, amount: 5, remaining: 7 },
+            BattleEvent::Healed { target: bob, amount: 3, remaining: 10 },
+        ], battle.take_events(), "Healing must not exceed maximum health.");
+    }
+
+    #[test]
+    fn test_action_points() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.allies.members[0].max_action_points = 3;
+        battle.start_round();
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+        battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+        assert_eq!(1, battle.allies.members[0].action_points());
+        assert_eq!(Err(BattleError::NotEnoughActionPoints(NotEnoughActionPoints { cost: 2, available: 1 })),
+            battle.resolve_action(&mut rng, alice, BattleAction::Attack { target: vim }),
+            "A 2-cost action must be rejected with 1 action point left.");
+        assert!(battle.take_events().iter().all(|event| !matches!(event, BattleEvent::AttackDeclared { .. })),
+            "Rejected actions must not happen.");
+        battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+        assert_eq!(Err(BattleError::NotEnoughActionPoints(NotEnoughActionPoints { cost: 1, available: 0 })),
+            battle.resolve_action(&mut rng, alice, BattleAction::Defend),
+            "Nothing more must be affordable once action points run out.");
+
+        battle.start_round();
+        assert_eq!(3, battle.allies.members[0].action_points(),
+            "Action points must be restored when a round starts.");
+        assert!(battle.resolve_action(&mut rng, alice, BattleAction::Attack { target: vim }).is_ok());
+    }
+
+    #[test]
+    fn test_run_round_spends_action_points() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        battle.allies.members[0].max_action_points = 1;
+
+        battle.run_round(&mut rng);
+        let alice_attacked = battle.take_events().iter()
+            .any(|event| matches!(event, BattleEvent::AttackDeclared { attacker, .. } if *attacker == alice));
+        assert!(!alice_attacked,
+            "Combatants who can't afford an attack must skip their turn.");
+        assert_eq!(0, battle.allies.members[1].action_points(),
+            "Attacks in a round must spend action points.");
+    }
+
+    #[test]
+    fn test_run_round_ends_round_after_rout() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        battle.allies.members[0].modifiers.push(StatModifier::new("Haste".to_string(), CombatStats::new(), 1));
+        battle.enemies.members[0].health.damage(10);
+
+        assert_eq!(BattleState::Won(Side::Allies), battle.run_round(&mut rng));
+        assert!(battle.take_events().iter().any(|event| matches!(event, BattleEvent::ModifierExpired { .. })),
+            "The round must still end when a side has no one left to attack.");
+    }
+
+    #[test]
+    fn test_defend_action() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+        assert!(battle.allies.members[0].guarding);
+        assert_eq!(0, battle.allies.members[0].effective_stats().defense,
+            "Defending must not raise defense on top of guarding.");
+
+        battle.start_round();
+        battle.resolve_action(&mut rng, alice, BattleAction::Attack { target: vim }).unwrap();
+        assert!(battle.allies.members[0].guarding,
+            "Guarding must last until the next hit, not the next action.");
+    }
+
+    #[test]
+    fn test_battle_uses_rules() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.enemies.members[0].stats.defense = 50;
+        battle.rules = BattleRules { 
+            die_sides: 1, 
+            crit_threshold: 0, 
+            min_damage: Some(2), 
+            ..Default::default() 
+        };
+
+        let outcome = battle.attack(&mut rng, alice, vim).unwrap();
+        assert_eq!(1, outcome.dice_roll, "Rolls must use the die from the rules.");
+        assert_eq!(AttackResult::DirectHit, outcome.result);
+        assert_eq!(Some(2), outcome.damage, "Damage must be raised to the minimum.");
+    }
+
+    #[test]
+    fn test_defeated_combatant_lingers() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let bob = CombatantId { side: Side::Allies, index: 1 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.allies.members[0].defeat_timer = 2;
+        battle.allies.members[0].health.damage(9);
+        battle.enemies.members[0].give_weapon(Weapon::new("Claws".to_string(), 200, 8));
+
+        battle.attack(&mut rng, vim, alice).unwrap();
+        assert_eq!(HealthStatus::Defeated, battle.allies.members[0].health.check_status());
+        for _ in 0..2 {
+            assert!(battle.tick_defeated().is_empty(),
+                "A defeated combatant must linger until their timer runs out.");
+            assert_eq!(vec![bob], battle.valid_targets(vim),
+                "A dying combatant must not be targetable.");
+        }
+        assert_eq!(vec![alice], battle.tick_defeated());
+        assert!(battle.tick_defeated().is_empty());
+
+        assert_eq!(2, battle.allies.members.len(),
+            "Defeated combatants must stay in place so ids stay valid.");
+        assert_eq!(Some("Bob"), battle.combatant(bob).map(|bob| bob.name.as_str()));
+        let removed = battle.allies.remove_defeated();
+        assert_eq!("Alice", removed[0].name);
+        assert_eq!(1, battle.allies.members.len());
+    }
+
+    #[test]
+    fn test_degenerate_die() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.rules.die_sides = -3;
+
+        let outcome = battle.attack(&mut rng, alice, vim).unwrap();
+        assert_eq!(1, outcome.dice_roll,
+            "A die with fewer than one side must roll as one-sided.");
+    }
+
+    #[test]
+    fn test_escape_ignores_rules_die() {
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let escapes = (0..20)
+            .filter(|&seed| {
+                let mut battle = test_battle();
+                battle.rules.die_sides = 6;
+                battle.allies.members[0].stats.speed = -4;
+                assert_eq!(10, battle.escape_chance(alice));
+                battle.resolve_action(&mut SeededRng::new(seed), alice, BattleAction::Escape).unwrap();
+                battle.state() == BattleState::Fled(Side::Allies)
+            })
+            .count();
+        assert!(escapes < 20,
+            "A 10% escape chance must not become certain with a smaller die.");
+    }
+
+    #[test]
+    fn test_damage_variance_rolled() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(3);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        let mut axe = Weapon::new("Axe".to_string(), 200, 10);
+        axe.damage_variance = 2;
+        battle.allies.members[0].give_weapon(axe);
+        battle.enemies.members[0].health = crate::combatant::Health::new(1000);
+        battle.rules.crit_threshold = 0;
+
+        let mut rolls = Vec::new();
+        for _ in 0..50 {
+            let outcome = battle.attack(&mut rng, alice, vim).unwrap();
+            let damage_roll = outcome.damage_roll.unwrap();
+            assert!((8..=12).contains(&damage_roll),
+                "Damage must be rolled within the weapon's variance, got {damage_roll}.");
+            assert_eq!(Some(damage_roll), outcome.damage,
+                "Damage must be calculated from the rolled value.");
+            rolls.push(damage_roll);
+        }
+        assert!(rolls.contains(&8) && rolls.contains(&12),
+            "Both ends of the range must be reachable.");
+    }
+
+    #[test]
+    fn test_dual_wield_attacks_twice() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.enemies.members[0].health = crate::combatant::Health::new(100);
+        battle.allies.members[0].equip_off_hand(OffHand::Weapon(Weapon::new("Dagger".to_string(), 80, 4))).unwrap();
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Attack { target: vim }).unwrap();
+        let attacks = battle.take_events().into_iter()
+            .filter(|event| matches!(event, BattleEvent::AttackResolved { .. }))
+            .count();
+        assert_eq!(2, attacks, "Dual-wielding must grant a second attack roll.");
+
+        battle.allies.members[0].take_off_hand();
+        battle.start_round();
+        battle.resolve_action(&mut rng, alice, BattleAction::Attack { target: vim }).unwrap();
+        let attacks = battle.take_events().into_iter()
+            .filter(|event| matches!(event, BattleEvent::AttackResolved { .. }))
+            .count();
+        assert_eq!(1, attacks);
+    }
+
+    #[test]
+    fn test_guard_consumed_by_hit() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        battle.enemies.members[0].give_weapon(Weapon::new("Claws".to_string(), 200, 8));
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+        assert!(battle.allies.members[0].guarding);
+
+        let outcome = battle.attack(&mut rng, vim, alice).unwrap();
+        let guarded = 8.0 * GUARD_MULTIPLIER;
+        let expected = (guarded * damage_multiplier(&battle.rules, &outcome.result).unwrap()) as i32;
+        assert_eq!(Some(expected), outcome.damage,
+            "Guarding must multiply the damage of the next hit.");
+        assert!(!battle.allies.members[0].guarding,
+            "Guarding must be used up by the first hit.");
+    }
+
+    #[test]
+    fn test_guard_against_spell() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        let caster = &mut battle.enemies.members[0];
+        caster.spells.push(Spell::damage("Bolt".to_string(), 3, 200, 8));
+        caster.max_mana = 3;
+        caster.restore_mana(3);
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Defend).unwrap();
+        battle.resolve_action(&mut rng, vim, BattleAction::Cast { spell: 0, target: alice }).unwrap();
+        let events = battle.take_events();
+        let result = events.iter()
+            .find_map(|event| match event {
+                BattleEvent::AttackResolved { result, .. } => Some(*result),
+                _ => None,
+            })
+            .unwrap();
+        let damage = events.iter()
+            .find_map(|event| match event {
+                BattleEvent::Damaged { damage, .. } => Some(*damage),
+                _ => None,
+            });
+        let expected = (8.0 * GUARD_MULTIPLIER * damage_multiplier(&battle.rules, &result).unwrap()) as i32;
+        assert_eq!(Some(expected), damage,
+            "Guarding must multiply the damage of spells too.");
+        assert!(!battle.allies.members[0].guarding,
+            "Guarding must be used up by a damaging spell.");
+    }
+
+    #[test]
+    fn test_cast_action() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let bob = CombatantId { side: Side::Allies, index: 1 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        let caster = &mut battle.allies.members[0];
+        caster.spells.push(Spell::damage("Bolt".to_string(), 3, 200, 4));
+        caster.spells.push(Spell::heal("Mend".to_string(), 2, 5));
+        caster.max_mana = 5;
+        caster.restore_mana(5);
+
+        assert_eq!(Err(BattleError::InvalidTarget(bob)),
+            battle.resolve_action(&mut rng, alice, BattleAction::Cast { spell: 0, target: bob }),
+            "Damaging spells must target opponents.");
+        assert_eq!(Err(BattleError::InvalidSpell(2)),
+            battle.resolve_action(&mut rng, alice, BattleAction::Cast { spell: 2, target: vim }));
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Cast { spell: 0, target: vim }).unwrap();
+        assert_eq!(2, battle.allies.members[0].mana(),
+            "Casting must spend the spell's mana cost.");
+        assert!(battle.enemies.members[0].health.current() < 10,
+            "Damaging spells must deal damage.");
+
+        assert_eq!(Err(BattleError::NotEnoughMana(NotEnoughMana { cost: 3, available: 2 })),
+            battle.resolve_action(&mut rng, alice, BattleAction::Cast { spell: 0, target: vim }));
+    }
+
+    #[test]
+    fn test_spell_effectiveness() {
+        let mut target = Combatant::new("Target".to_string());
+        target.element = Some(Element::Water);
+        let mut fireball = Spell::damage("Fireball".to_string(), 3, 80, 8);
+        fireball.element = Some(Element::Fire);
+        let mut mend = Spell::heal("Mend".to_string(), 2, 8);
+        mend.element = Some(Element::Fire);
+
+        assert_eq!(Some(4), calculate_spell_power(&AttackResult::DirectHit, &fireball, &target),
+            "Resisted spells must deal half damage.");
+        assert_eq!(Some(8), calculate_spell_power(&AttackResult::DirectHit, &mend, &target),
+            "Healing must not be affected by elements.");
+    }
+
+    #[test]
+    fn test_heal_spell_never_misses() {
+        let caster = Combatant::new("Caster".to_string());
+        let target = Combatant::new("Target".to_string());
+        let mend = Spell::heal("Mend".to_string(), 2, 5);
+
+        for dice_roll in 1..=100 {
+            assert_eq!(AttackResult::DirectHit, resolve_spell(dice_roll, &mend, &caster, &target),
+                "Healing spells must never miss.");
+        }
+    }
+
+    #[test]
+    fn test_describe_heal_spell() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let bob = CombatantId { side: Side::Allies, index: 1 };
+        let caster = &mut battle.allies.members[0];
+        caster.spells.push(Spell::heal("Mend".to_string(), 2, 5));
+        caster.max_mana = 2;
+        caster.restore_mana(2);
+        battle.allies.members[1].health.damage(6);
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Cast { spell: 0, target: bob }).unwrap();
+        let lines: Vec<String> = battle.take_events().iter()
+            .map(|event| battle.describe(event))
+            .collect();
+        assert_eq!(vec![
+            "Alice casts Mend on Bob".to_string(),
+            "Bob recovers 5 hit points.\nBob has 9 hit points.".to_string(),
+        ], lines, "Healing spells must not be described as hits.");
+    }
+
+    #[test]
+    fn test_escape_action() {
+        let mut battle = test_battle();
+        battle.allies.members[0].stats.speed = 5;
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Escape).unwrap();
+        assert_eq!(BattleState::Fled(Side::Allies), battle.state(),
+            "A much faster runner must always escape.");
+        assert_eq!(vec![BattleEvent::Fled { actor: alice }], battle.take_events());
+        assert_eq!(Err(BattleError::BattleOver),
+            battle.resolve_action(&mut rng, vim, BattleAction::Defend),
+            "No actions can be taken once a side has fled.");
+    }
+
+    #[test]
+    fn test_failed_escape() {
+        let mut battle = test_battle();
+        battle.enemies.members[0].stats.speed = 5;
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+
+        battle.resolve_action(&mut rng, alice, BattleAction::Escape).unwrap();
+        assert_eq!(BattleState::Ongoing, battle.state(),
+            "A much slower runner must never escape.");
+        assert_eq!(vec![BattleEvent::EscapeFailed { actor: alice }], battle.take_events());
+    }
+
+    #[test]
+    fn test_use_item_action() {
+        let mut battle = test_battle();
+        let mut rng = SeededRng::new(1);
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let inventory = &mut battle.allies.members[0].inventory;
+        inventory.add_item(Item::new("Pebble".to_string())).unwrap();
+        inventory.add_item(Item::with_effect("Herb".to_string(), ItemEffect::Heal(5))).unwrap();
+
+        assert_eq!(Err(BattleError::InvalidItem(0)),
+            battle.resolve_action(&mut rng, alice, BattleAction::UseItem { item: 0, target: alice }),
+            "Items without an effect must not be usable.");
+        assert_eq!(Err(BattleError::InvalidItem(2)),
+            battle.resolve_action(&mut rng, alice, BattleAction::UseItem { item: 2, target: alice }));
+
+        battle.resolve_action(&mut rng, alice, BattleAction::UseItem { item: 1, target: alice }).unwrap();
+        assert_eq!(1, battle.allies.members[0].inventory.items().len(),
+            "Using an item must consume it.");
+    }
+
+    #[test]
+    fn test_roll_loot_once() {
+        let mut battle = test_battle();
+        battle.enemies.members[0].drop_table = DropTable::new(vec![
+            (1, Loot::Gold(5)),
+            (1, Loot::Item(Item::new("Pebble".to_string()))),
+        ]);
+        battle.enemies.members[0].health.damage(10);
+        let mut rng = SeededRng::new(3);
+
+        let first = battle.roll_loot(&mut rng);
+        assert!(!first.is_empty());
+        for _ in 0..10 {
+            assert_eq!(first, battle.roll_loot(&mut rng),
+                "Loot must only be rolled once per battle.");
+        }
+    }
+
+    #[test]
+    fn test_no_loot_on_defeat() {
+        let mut battle = test_battle();
+        battle.enemies.members[0].drop_table = DropTable::new(vec![(1, Loot::Gold(5))]);
+        battle.allies.members[0].health.damage(10);
+        battle.allies.members[1].health.damage(10);
+        let mut rng = SeededRng::new(3);
+
+        assert!(battle.roll_loot(&mut rng).is_empty(),
+            "Nothing must drop when the allies lose.");
+        assert_eq!(None, battle.loot());
+    }
+
+    #[test]
+    fn test_award_xp_on_victory() {
+        let mut battle = test_battle();
+        battle.enemies.members[0].xp_reward = 12;
+        battle.allies.members[1].health.damage(10);
+        let table = XpTable::new(vec![10, 30]);
+
+        assert_eq!(0, battle.award_xp(&table),
+            "Experience must not be awarded while the battle is ongoing.");
+
+        battle.enemies.members[0].health.damage(10);
+        assert_eq!(12, battle.award_xp(&table));
+        assert_eq!(2, battle.allies.members[0].level());
+        assert_eq!(0, battle.allies.members[1].experience(),
+            "Defeated allies must not earn experience.");
+
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        assert_eq!(vec![
+            BattleEvent::ExperienceGained { target: alice, amount: 12 },
+            BattleEvent::LevelUp { target: alice, level: 2 },
+        ], battle.take_events());
+    }
+
+    #[cfg(feature = "test-util")]
+    fn mirrored_pair() -> (Combatant, Combatant) {
+        let mut a = Combatant::new("Alice".to_string());
+        let mut b = Combatant::new("Vim".to_string());
+        for combatant in [&mut a, &mut b] {
+            combatant.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+            combatant.stats.accuracy = 5;
+            combatant.stats.evasion = 3;
+            combatant.stats.strength = 2;
+            combatant.stats.defense = 1;
+        }
+        (a, b)
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    fn test_symmetric_setup_passes() {
+        let (a, mut b) = mirrored_pair();
+        // Current health isn't part of the formulas, so it can differ.
+        b.health.damage(4);
+
+        assert_symmetric(&a, &b);
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    #[should_panic(expected = "must not change the outcome")]
+    fn test_asymmetric_formula_fails() {
+        use crate::combatant::HealthStatus;
+
+        let (a, mut b) = mirrored_pair();
+        b.health.damage(4);
+
+        // A formula that rewards hitting hurt defenders favors Alice.
+        assert_symmetric_by(&a, &b, |attacker, defender| {
+            let bonus = match defender.health.check_status() {
+                HealthStatus::Hurt => 10,
+                _ => 0,
+            };
+            calculate_hit_rate(attacker, defender).map(|rate| rate + bonus)
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    #[should_panic(expected = "must both be guarding")]
+    fn test_mismatched_guarding_fails() {
+        let (a, mut b) = mirrored_pair();
+        b.guarding = true;
+
+        assert_symmetric(&a, &b);
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    #[should_panic(expected = "must have the same stats")]
+    fn test_mismatched_stats_fail() {
+        let (a, mut b) = mirrored_pair();
+        b.stats.evasion = 0;
+
+        assert_symmetric(&a, &b);
+    }
+}
@@ -0,0 +1,343 @@
+//! This module specifies an optional tactical layer for a [`Battle`]: a
+//! [`Grid`] of tiles that combatants stand on and move across, where attacks
+//! are only legal against targets within the reach of the attacker's weapon.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::battle::{Battle, CombatantId};
+use crate::combatant::Combatant;
+
+/// How many tiles a combatant with no speed can move in one turn. Each point
+/// of effective speed adds one more.
+pub const BASE_MOVEMENT: i32 = 3;
+
+/// A tile on a [`Grid`], counted from the top-left corner.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Position {
+    /// The column of the tile.
+    pub x: i32,
+    /// The row of the tile.
+    pub y: i32,
+}
+
+impl Position {
+    /// Constructs a position from a column and row.
+    pub fn new(x: i32, y: i32) -> Position {
+        Position { x, y }
+    }
+
+    /// Returns how many steps apart two tiles are, moving only horizontally
+    /// and vertically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::battle::grid::Position;
+    ///
+    /// assert_eq!(5, Position::new(0, 0).distance(Position::new(2, 3)));
+    /// ```
+    pub fn distance(self, other: Position) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Returns the four tiles directly next to this one. Some of them may be
+    /// off the grid.
+    fn neighbors(self) -> [Position; 4] {
+        [
+            Position::new(self.x + 1, self.y),
+            Position::new(self.x - 1, self.y),
+            Position::new(self.x, self.y + 1),
+            Position::new(self.x, self.y - 1),
+        ]
+    }
+}
+
+/// A list of reasons a change to a [`Grid`] can be rejected.
+#[derive(PartialEq, Debug)]
+pub enum GridError {
+    /// The position isn't on the grid.
+    OutOfBounds(Position),
+    /// Another combatant is already standing on the position.
+    Occupied(Position),
+    /// The combatant hasn't been placed on the grid.
+    NotPlaced(CombatantId),
+    /// The combatant can't move that far this turn.
+    OutOfMovementRange(Position),
+}
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::OutOfBounds(position) => write!(f, "{position:?} is off the grid"),
+            GridError::Occupied(position) => write!(f, "{position:?} is already occupied"),
+            GridError::NotPlaced(id) => write!(f, "{id:?} isn't on the grid"),
+            GridError::OutOfMovementRange(position) => write!(f, "{position:?} is too far away"),
+        }
+    }
+}
+impl Error for GridError {}
+
+/// Returns how many tiles the combatant can move in one turn.
+///
+/// # Examples
+///
+/// ```
+/// use druid_game::battle::grid;
+/// use druid_game::combatant::Combatant;
+///
+/// let mut scout = Combatant::new("Scout".to_string());
+/// scout.stats.speed = 2;
+/// assert_eq!(5, grid::movement_range(&scout));
+/// ```
+pub fn movement_range(combatant: &Combatant) -> u32 {
+    (BASE_MOVEMENT + combatant.effective_stats().speed).max(0) as u32
+}
+
+/// A rectangular battlefield where each tile holds at most one combatant.
+///
+/// The grid only tracks where combatants stand. The combatants themselves,
+/// and whether they are still standing, are owned by the [`Battle`].
+#[derive(Debug, Clone)]
+pub struct Grid {
+    width: i32,
+    height: i32,
+    positions: HashMap<CombatantId, Position>,
+}
+
+impl Grid {
+    /// Constructs an empty grid with the given number of columns and rows.
+    pub fn new(width: i32, height: i32) -> Grid {
+        Grid {
+            width,
+            height,
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Returns `true` if the position is on the grid.
+    pub fn contains(&self, position: Position) -> bool {
+        (0..self.width).contains(&position.x) && (0..self.height).contains(&position.y)
+    }
+
+    /// Returns the position of the given combatant, if they are on the grid.
+    pub fn position(&self, id: CombatantId) -> Option<Position> {
+        self.positions.get(&id).copied()
+    }
+
+    /// Returns the combatant standing on the given position, if any.
+    pub fn occupant(&self, position: Position) -> Option<CombatantId> {
+        self.positions.iter()
+            .find(|(_, &occupied)| occupied == position)
+            .map(|(&id, _)| id)
+    }
+
+    /// Puts a combatant on the given position, ignoring movement range. If
+    /// they were already on the grid, they are moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GridError`] if the position is off the grid or occupied by
+    /// someone else.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::battle::{CombatantId, Side};
+    /// use druid_game::battle::grid::{Grid, GridError, Position};
+    ///
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// let vim = CombatantId { side: Side::Enemies, index: 0 };
+    /// let mut grid = Grid::new(4, 4);
+    ///
+    /// assert!(grid.place(alice, Position::new(0, 0)).is_ok());
+    /// assert_eq!(Err(GridError::Occupied(Position::new(0, 0))), grid.place(vim, Position::new(0, 0)));
+    /// assert_eq!(Err(GridError::OutOfBounds(Position::new(4, 0))), grid.place(vim, Position::new(4, 0)));
+    /// ```
+    pub fn place(&mut self, id: CombatantId, position: Position) -> Result<(), GridError> {
+        if !self.contains(position) {
+            return Err(GridError::OutOfBounds(position));
+        }
+        if self.occupant(position).is_some_and(|occupant| occupant != id) {
+            return Err(GridError::Occupied(position));
+        }
+        self.positions.insert(id, position);
+        Ok(())
+    }
+
+    /// Takes a combatant off the grid, returning where they stood.
+    pub fn remove(&mut self, id: CombatantId) -> Option<Position> {
+        self.positions.remove(&id)
+    }
+
+    /// Returns every tile the combatant could move to with the given number
+    /// of steps, walking around occupied tiles. Their current position is
+    /// included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::battle::{CombatantId, Side};
+    /// use druid_game::battle::grid::{Grid, Position};
+    ///
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// let mut grid = Grid::new(3, 3);
+    /// grid.place(alice, Position::new(0, 0)).unwrap();
+    ///
+    /// let tiles = grid.reachable_tiles(alice, 1);
+    /// assert_eq!(3, tiles.len());
+    /// assert!(tiles.contains(&Position::new(1, 0)));
+    /// ```
+    pub fn reachable_tiles(&self, id: CombatantId, steps: u32) -> Vec<Position> {
+        let Some(start) = self.position(id) else {
+            return Vec::new();
+        };
+
+        let mut reached = vec![start];
+        let mut visited = HashSet::from([start]);
+        let mut frontier = VecDeque::from([(start, 0)]);
+        while let Some((position, distance)) = frontier.pop_front() {
+            if distance == steps {
+                continue;
+            }
+            for neighbor in position.neighbors() {
+                if !self.contains(neighbor) || self.occupant(neighbor).is_some() {
+                    continue;
+                }
+                if visited.insert(neighbor) {
+                    reached.push(neighbor);
+                    frontier.push_back((neighbor, distance + 1));
+                }
+            }
+        }
+        reached
+    }
+
+    /// Moves a combatant to the given position, as long as it is within
+    /// their [`movement_range`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GridError`] if the combatant isn't in the battle or on the
+    /// grid, or the position can't be reached this turn.
+    pub fn move_combatant(&mut self, battle: &Battle, id: CombatantId, destination: Position) -> Result<(), GridError> {
+        let combatant = battle.combatant(id).ok_or(GridError::NotPlaced(id))?;
+        if self.position(id).is_none() {
+            return Err(GridError::NotPlaced(id));
+        }
+        if !self.contains(destination) {
+            return Err(GridError::OutOfBounds(destination));
+        }
+        if self.occupant(destination).is_some_and(|occupant| occupant != id) {
+            return Err(GridError::Occupied(destination));
+        }
+        if !self.reachable_tiles(id, movement_range(combatant)).contains(&destination) {
+            return Err(GridError::OutOfMovementRange(destination));
+        }
+        self.place(id, destination)
+    }
+
+    /// Returns `true` if the attacker may attack the target: the target must
+    /// be one of their [`valid_targets`](Battle::valid_targets), and stand
+    /// within the reach of the attacker's weapon. Unarmed attackers can only
+    /// reach adjacent tiles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::battle::{Battle, CombatantId, Side};
+    /// use druid_game::battle::grid::{Grid, Position};
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    ///
+    /// let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    /// let battle = Battle::new(allies, enemies);
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// let vim = CombatantId { side: Side::Enemies, index: 0 };
+    ///
+    /// let mut grid = Grid::new(4, 4);
+    /// grid.place(alice, Position::new(0, 0)).unwrap();
+    /// grid.place(vim, Position::new(2, 0)).unwrap();
+    /// assert!(!grid.in_reach(&battle, alice, vim));
+    ///
+    /// grid.place(vim, Position::new(1, 0)).unwrap();
+    /// assert!(grid.in_reach(&battle, alice, vim));
+    /// ```
+    pub fn in_reach(&self, battle: &Battle, attacker: CombatantId, target: CombatantId) -> bool {
+        let (Some(from), Some(to)) = (self.position(attacker), self.position(target)) else {
+            return false;
+        };
+        let Some(combatant) = battle.combatant(attacker) else {
+            return false;
+        };
+        let reach = combatant.current_weapon().as_ref().map_or(1, |weapon| weapon.reach);
+        battle.valid_targets(attacker).contains(&target) && from.distance(to) <= reach
+    }
+
+    /// Returns every target the attacker may attack from where they stand.
+    pub fn targets_in_reach(&self, battle: &Battle, attacker: CombatantId) -> Vec<CombatantId> {
+        battle.valid_targets(attacker).into_iter()
+            .filter(|&target| self.in_reach(battle, attacker, target))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::battle::Side;
+    use crate::combat::Party;
+    use crate::weapon::Weapon;
+
+    const ALICE: CombatantId = CombatantId { side: Side::Allies, index: 0 };
+    const VIM: CombatantId = CombatantId { side: Side::Enemies, index: 0 };
+
+    fn test_battle() -> Battle {
+        let allies = Party::new(vec![Combatant::new("Alice".to_string())]);
+        let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+        Battle::new(allies, enemies)
+    }
+
+    #[test]
+    fn test_movement_blocked_by_occupants() {
+        let battle = test_battle();
+        let mut grid = Grid::new(3, 1);
+        grid.place(ALICE, Position::new(0, 0)).unwrap();
+        grid.place(VIM, Position::new(1, 0)).unwrap();
+
+        assert_eq!(vec![Position::new(0, 0)], grid.reachable_tiles(ALICE, 5),
+            "Combatants must not walk through occupied tiles.");
+        assert_eq!(Err(GridError::OutOfMovementRange(Position::new(2, 0))),
+            grid.move_combatant(&battle, ALICE, Position::new(2, 0)));
+    }
+
+    #[test]
+    fn test_move_within_range() {
+        let battle = test_battle();
+        let mut grid = Grid::new(8, 8);
+        grid.place(ALICE, Position::new(0, 0)).unwrap();
+
+        assert!(grid.move_combatant(&battle, ALICE, Position::new(2, 1)).is_ok());
+        assert_eq!(Some(ALICE), grid.occupant(Position::new(2, 1)));
+        assert_eq!(None, grid.occupant(Position::new(0, 0)),
+            "Moving must free the previous tile.");
+        assert_eq!(Err(GridError::OutOfMovementRange(Position::new(6, 6))),
+            grid.move_combatant(&battle, ALICE, Position::new(6, 6)));
+    }
+
+    #[test]
+    fn test_reach_uses_weapon() {
+        let mut battle = test_battle();
+        let mut spear = Weapon::new("Spear".to_string(), 70, 6);
+        spear.reach = 2;
+        battle.allies.members[0].give_weapon(spear);
+        let mut grid = Grid::new(4, 4);
+        grid.place(ALICE, Position::new(0, 0)).unwrap();
+        grid.place(VIM, Position::new(1, 1)).unwrap();
+
+        assert_eq!(vec![VIM], grid.targets_in_reach(&battle, ALICE));
+        assert!(!grid.in_reach(&battle, VIM, ALICE),
+            "Unarmed combatants must only reach adjacent tiles.");
+    }
+}
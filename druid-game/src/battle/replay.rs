@@ -0,0 +1,239 @@
+//! This module specifies [`BattleReplay`]s: records of a battle's RNG seed
+//! and every step taken in it, which can be re-simulated to produce exactly
+//! the same [`BattleEvent`]s. Replays are handy for bug reports and for
+//! spectating battles after the fact.
+
+use crate::battle::{Battle, BattleAction, BattleError, BattleEvent, BattleState, CombatantId};
+use crate::service::SeededRng;
+
+/// Something that happened in a recorded battle that uses up random numbers
+/// or changes its state.
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReplayStep {
+    /// A combatant took an action with [`Battle::resolve_action`].
+    Action {
+        /// The combatant that acted.
+        actor: CombatantId,
+        /// What they did.
+        action: BattleAction,
+    },
+    /// The round started with [`Battle::start_round`].
+    StartRound,
+    /// The turn order was rolled with [`Battle::turn_order`].
+    TurnOrder,
+    /// The round ended with [`Battle::end_round`].
+    EndRound,
+    /// A whole round was played out with [`Battle::run_round`].
+    RunRound,
+}
+
+/// The seed and steps of a recorded battle. Made with a [`ReplayRecorder`].
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BattleReplay {
+    /// The seed of the [`SeededRng`] the battle was played with.
+    pub seed: u64,
+    /// Every step taken in the battle, in order.
+    pub steps: Vec<ReplayStep>,
+}
+
+impl BattleReplay {
+    /// Re-simulates the recorded steps on the given battle, returning every
+    /// [`BattleEvent`] they produce.
+    ///
+    /// The battle must start out just like the recorded one did, with the
+    /// same parties and rules, for the events to match.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BattleError`] if a recorded action is rejected, which
+    /// means the battle didn't start out like the recorded one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::battle::{Battle, BattleAction, CombatantId, Side};
+    /// use druid_game::battle::replay::ReplayRecorder;
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::weapon::Weapon;
+    ///
+    /// fn new_battle() -> Battle {
+    ///     let mut alice = Combatant::new("Alice".to_string());
+    ///     alice.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+    ///     let allies = Party::new(vec![alice]);
+    ///     let enemies = Party::new(vec![Combatant::new("Vim".to_string())]);
+    ///     Battle::new(allies, enemies)
+    /// }
+    /// let alice = CombatantId { side: Side::Allies, index: 0 };
+    /// let vim = CombatantId { side: Side::Enemies, index: 0 };
+    ///
+    /// let mut battle = new_battle();
+    /// let mut recorder = ReplayRecorder::new(42);
+    /// recorder.resolve_action(&mut battle, alice, BattleAction::Attack { target: vim }).unwrap();
+    /// let replay = recorder.finish();
+    ///
+    /// let events = replay.replay(&mut new_battle()).unwrap();
+    /// assert_eq!(battle.take_events(), events);
+    /// ```
+    pub fn replay(&self, battle: &mut Battle) -> Result<Vec<BattleEvent>, BattleError> {
+        let mut rng = SeededRng::new(self.seed);
+        let mut events = Vec::new();
+        for step in &self.steps {
+            match *step {
+                ReplayStep::Action { actor, action } => battle.resolve_action(&mut rng, actor, action)?,
+                ReplayStep::StartRound => battle.start_round(),
+                ReplayStep::TurnOrder => {
+                    battle.turn_order(&mut rng);
+                },
+                ReplayStep::EndRound => battle.end_round(),
+                ReplayStep::RunRound => {
+                    battle.run_round(&mut rng);
+                },
+            }
+            events.append(&mut battle.take_events());
+        }
+        Ok(events)
+    }
+}
+
+/// Plays a battle with a [`SeededRng`], recording each step into a
+/// [`BattleReplay`].
+///
+/// Steps taken on the battle without going through the recorder aren't
+/// recorded, and neither are random numbers drawn from another RNG, so the
+/// battle should only be advanced through the recorder while recording.
+#[derive(Debug)]
+pub struct ReplayRecorder {
+    rng: SeededRng,
+    replay: BattleReplay,
+}
+
+impl ReplayRecorder {
+    /// Starts recording a battle played with the given seed.
+    pub fn new(seed: u64) -> ReplayRecorder {
+        ReplayRecorder {
+            rng: SeededRng::new(seed),
+            replay: BattleReplay { seed, steps: Vec::new() },
+        }
+    }
+
+    /// Has a combatant take an action like [`Battle::resolve_action`],
+    /// recording it if it is accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BattleError`] if the action is rejected. Rejected actions
+    /// aren't recorded.
+    pub fn resolve_action(&mut self, battle: &mut Battle, actor: CombatantId, action: BattleAction) -> Result<(), BattleError> {
+        battle.resolve_action(&mut self.rng, actor, action)?;
+        self.replay.steps.push(ReplayStep::Action { actor, action });
+        Ok(())
+    }
+
+    /// Starts the round like [`Battle::start_round`], recording it.
+    pub fn start_round(&mut self, battle: &mut Battle) {
+        self.replay.steps.push(ReplayStep::StartRound);
+        battle.start_round();
+    }
+
+    /// Rolls the turn order like [`Battle::turn_order`], recording the roll.
+    pub fn turn_order(&mut self, battle: &Battle) -> Vec<CombatantId> {
+        self.replay.steps.push(ReplayStep::TurnOrder);
+        battle.turn_order(&mut self.rng)
+    }
+
+    /// Ends the round like [`Battle::end_round`], recording it.
+    pub fn end_round(&mut self, battle: &mut Battle) {
+        self.replay.steps.push(ReplayStep::EndRound);
+        battle.end_round();
+    }
+
+    /// Plays out a whole round like [`Battle::run_round`], recording it.
+    pub fn run_round(&mut self, battle: &mut Battle) -> BattleState {
+        self.replay.steps.push(ReplayStep::RunRound);
+        battle.run_round(&mut self.rng)
+    }
+
+    /// Borrows the replay recorded so far.
+    pub fn replay(&self) -> &BattleReplay {
+        &self.replay
+    }
+
+    /// Stops recording, returning the finished replay.
+    pub fn finish(self) -> BattleReplay {
+        self.replay
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::battle::Side;
+    use crate::combat::Party;
+    use crate::combatant::{Combatant, StatModifier};
+    use crate::weapon::Weapon;
+
+    fn test_battle() -> Battle {
+        let mut alice = Combatant::new("Alice".to_string());
+        alice.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+        alice.modifiers.push(StatModifier::new("Haste".to_string(), Default::default(), 1));
+        let mut bob = Combatant::new("Bob".to_string());
+        bob.give_weapon(Weapon::new("Club".to_string(), 60, 6));
+        let mut vim = Combatant::new("Vim".to_string());
+        vim.give_weapon(Weapon::new("Claws".to_string(), 50, 3));
+        Battle::new(Party::new(vec![alice, bob]), Party::new(vec![vim]))
+    }
+
+    #[test]
+    fn test_replay_matches_recording() {
+        let vim = CombatantId { side: Side::Enemies, index: 0 };
+        let mut battle = test_battle();
+        let mut recorder = ReplayRecorder::new(7);
+
+        while battle.state() == BattleState::Ongoing {
+            recorder.start_round(&mut battle);
+            for actor in recorder.turn_order(&battle) {
+                let target = match actor.side {
+                    Side::Allies => vim,
+                    Side::Enemies => CombatantId { side: Side::Allies, index: 0 },
+                };
+                let _ = recorder.resolve_action(&mut battle, actor, BattleAction::Attack { target });
+            }
+            recorder.end_round(&mut battle);
+        }
+        let recorded = battle.take_events();
+        let replay = recorder.finish();
+
+        assert_eq!(Ok(recorded), replay.replay(&mut test_battle()),
+            "Replaying must produce exactly the same events.");
+    }
+
+    #[test]
+    fn test_replay_run_round() {
+        let mut battle = test_battle();
+        let mut recorder = ReplayRecorder::new(11);
+
+        while recorder.run_round(&mut battle) == BattleState::Ongoing {}
+        let recorded = battle.take_events();
+        let replay = recorder.finish();
+
+        assert!(!replay.steps.is_empty(),
+            "Rounds played with run_round must be recorded.");
+        assert_eq!(Ok(recorded), replay.replay(&mut test_battle()),
+            "Replaying must produce exactly the same events.");
+    }
+
+    #[test]
+    fn test_replay_rejects_mismatched_battle() {
+        let alice = CombatantId { side: Side::Allies, index: 0 };
+        let replay = BattleReplay {
+            seed: 1,
+            steps: vec![ReplayStep::Action { actor: alice, action: BattleAction::Defend }],
+        };
+        let mut battle = Battle::new(Party::new(Vec::new()), Party::new(Vec::new()));
+
+        assert_eq!(Err(BattleError::InvalidActor(alice)), replay.replay(&mut battle));
+    }
+}
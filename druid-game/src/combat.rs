@@ -0,0 +1,328 @@
+//! This module specifies the [`Party`] type, used to group combatants who
+//! fight on the same side, and the [`Row`]s they line up in.
+
+use crate::combatant::{Combatant, HealthStatus};
+
+/// How much morale each surviving member of a party loses when one of their
+/// allies is defeated.
+pub const MORALE_LOSS_ON_ALLY_DEFEAT: i32 = 5;
+
+/// Where a combatant stands in their party's formation. Combatants in the 
+/// back row are further from the fight, so they are harder to reach, but 
+/// also need more reach to hit anyone.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Row {
+    /// The row closest to the opposing party.
+    #[default]
+    Front,
+    /// The row behind the front row.
+    Back,
+}
+
+impl Row {
+    /// Returns how many steps the row is behind the front line.
+    pub fn depth(self) -> u32 {
+        match self {
+            Row::Front => 0,
+            Row::Back => 1,
+        }
+    }
+}
+
+/// A group of combatants fighting together, along with the resources they
+/// share.
+#[derive(Default)]
+pub struct Party {
+    /// The combatants that belong to the party.
+    pub members: Vec<Combatant>,
+    /// Currency shared by the whole party.
+    pub gold: i32,
+    /// A pool of mana that any member of the party can draw from.
+    pub mana: i32,
+}
+
+impl Party {
+    /// Initializes a party with the given members and no shared resources.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    ///
+    /// let party = Party::new(vec![
+    ///     Combatant::new("Alice".to_string()),
+    ///     Combatant::new("Bob".to_string()),
+    /// ]);
+    /// assert_eq!(2, party.members.len());
+    /// ```
+    pub fn new(members: Vec<Combatant>) -> Party {
+        Party {
+            members,
+            gold: 0,
+            mana: 0,
+        }
+    }
+
+    /// Returns an iterator over the members of the party that have not been
+    /// defeated. Defeated members that are still waiting to be removed are 
+    /// excluded, so they can't be targeted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    ///
+    /// let mut party = Party::new(vec![
+    ///     Combatant::new("Alice".to_string()),
+    ///     Combatant::new("Bob".to_string()),
+    /// ]);
+    /// party.members[0].health.damage(10);
+    ///
+    /// let living: Vec<String> = party.living_members()
+    ///     .map(|member| member.name.clone())
+    ///     .collect();
+    /// assert_eq!(vec!["Bob".to_string()], living);
+    /// ```
+    pub fn living_members(&self) -> impl Iterator<Item = &Combatant> {
+        self.members.iter()
+            .filter(|member| member.health.check_status() != HealthStatus::Defeated)
+    }
+
+    /// Removes members that were defeated and have finished their 
+    /// [`defeat_timer`], returning them. Defeated members whose timer hasn't 
+    /// run out stay in the party, and their countdown ticks by one as with 
+    /// [`Combatant::tick_defeat`].
+    ///
+    /// Removing members shifts the indices of those after them, which would 
+    /// invalidate any [`CombatantId`]s pointing into the party. Only call 
+    /// this between battles; during a battle, use [`Battle::tick_defeated`] 
+    /// instead, which keeps defeated combatants in place.
+    ///
+    /// [`CombatantId`]: crate::battle::CombatantId
+    /// [`Battle::tick_defeated`]: crate::battle::Battle::tick_defeated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    ///
+    /// let mut vim = Combatant::new("Vim".to_string());
+    /// vim.defeat_timer = 1;
+    /// let mut party = Party::new(vec![vim]);
+    /// party.members[0].health.damage(10);
+    ///
+    /// // Vim lingers for one call while the defeat animation plays...
+    /// assert!(party.remove_defeated().is_empty());
+    /// assert_eq!(1, party.members.len());
+    ///
+    /// // ...and is removed on the next.
+    /// let removed = party.remove_defeated();
+    /// assert_eq!("Vim", removed[0].name);
+    /// assert!(party.members.is_empty());
+    /// ```
+    ///
+    /// [`defeat_timer`]: Combatant::defeat_timer
+    pub fn remove_defeated(&mut self) -> Vec<Combatant> {
+        let mut removed = Vec::new();
+        let mut remaining = Vec::with_capacity(self.members.len());
+        for mut member in self.members.drain(..) {
+            member.tick_defeat();
+            if member.defeat_finished() {
+                removed.push(member);
+            }
+            else {
+                remaining.push(member);
+            }
+        }
+        self.members = remaining;
+        removed
+    }
+
+    /// Lowers the morale of every living member of the party by 
+    /// [`MORALE_LOSS_ON_ALLY_DEFEAT`]. Call this whenever a member of the 
+    /// party is defeated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    ///
+    /// let mut party = Party::new(vec![
+    ///     Combatant::new("Alice".to_string()),
+    ///     Combatant::new("Bob".to_string()),
+    /// ]);
+    ///
+    /// party.members[0].health.damage(10);
+    /// party.on_member_defeated();
+    /// assert_eq!(-5, party.members[1].morale);
+    /// ```
+    pub fn on_member_defeated(&mut self) {
+        for member in self.members.iter_mut() {
+            if member.health.check_status() != HealthStatus::Defeated {
+                member.morale -= MORALE_LOSS_ON_ALLY_DEFEAT;
+            }
+        }
+    }
+
+    /// Returns the sum of the current health of every member of the party.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    ///
+    /// let mut party = Party::new(vec![
+    ///     Combatant::new("Alice".to_string()),
+    ///     Combatant::new("Bob".to_string()),
+    /// ]);
+    /// party.members[1].health.damage(3);
+    ///
+    /// assert_eq!(17, party.total_health());
+    /// ```
+    pub fn total_health(&self) -> i32 {
+        self.members.iter()
+            .map(|member| member.health.current())
+            .sum()
+    }
+
+    /// Returns `true` if every member of the party has been defeated.
+    ///
+    /// An empty party is considered wiped, since it has nobody left to fight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::combat::Party;
+    /// use druid_game::combatant::Combatant;
+    ///
+    /// let mut party = Party::new(vec![Combatant::new("Alice".to_string())]);
+    /// assert!(!party.is_wiped());
+    ///
+    /// party.members[0].health.damage(10);
+    /// assert!(party.is_wiped());
+    /// ```
+    pub fn is_wiped(&self) -> bool {
+        self.living_members().next().is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_party() -> Party {
+        Party::new(vec![
+            Combatant::new("Alice".to_string()),
+            Combatant::new("Bob".to_string()),
+            Combatant::new("Carol".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_not_wiped_while_any_member_stands() {
+        let mut party = test_party();
+        party.members[0].health.damage(10);
+        party.members[1].health.damage(10);
+
+        assert!(!party.is_wiped(),
+            "Party must not be wiped while a member is still standing.");
+        assert_eq!(1, party.living_members().count());
+    }
+
+    #[test]
+    fn test_wiped_when_all_defeated() {
+        let mut party = test_party();
+        for member in party.members.iter_mut() {
+            member.health.damage(10);
+        }
+
+        assert!(party.is_wiped(),
+            "Party must be wiped once every member is defeated.");
+    }
+
+    #[test]
+    fn test_total_health() {
+        let mut party = test_party();
+        party.members[0].health.damage(4);
+        party.members[2].health.damage(20);
+
+        assert_eq!(16, party.total_health(),
+            "Total health must sum the current health of every member.");
+    }
+
+    #[test]
+    fn test_defeated_member_lingers() {
+        let mut party = test_party();
+        party.members[0].defeat_timer = 2;
+        party.members[0].health.damage(10);
+
+        for _ in 0..2 {
+            assert!(party.remove_defeated().is_empty(),
+                "Defeated member must linger until their timer runs out.");
+            assert_eq!(3, party.members.len());
+            assert!(party.living_members().all(|member| member.name != "Alice"),
+                "A dying member must not be targetable.");
+        }
+
+        let removed = party.remove_defeated();
+        assert_eq!(1, removed.len());
+        assert_eq!("Alice", removed[0].name);
+        assert_eq!(2, party.members.len());
+    }
+
+    #[test]
+    fn test_healing_restarts_defeat_timer() {
+        let mut party = test_party();
+        party.members[0].defeat_timer = 1;
+        party.members[0].health.damage(10);
+        assert!(party.remove_defeated().is_empty());
+
+        party.members[0].health.heal(5);
+        assert!(party.remove_defeated().is_empty());
+        party.members[0].health.damage(10);
+        assert!(party.remove_defeated().is_empty(),
+            "Healing must restart the defeat countdown.");
+        assert_eq!(1, party.remove_defeated().len());
+    }
+
+    #[test]
+    fn test_remove_defeated_without_timer() {
+        let mut party = test_party();
+        party.members[1].health.damage(10);
+
+        let removed = party.remove_defeated();
+        assert_eq!(1, removed.len(),
+            "Defeated member without a timer must be removed immediately.");
+        assert_eq!(2, party.members.len());
+    }
+
+    #[test]
+    fn test_ally_defeat_lowers_hit_rate() {
+        use crate::battle::calculate_hit_rate;
+        use crate::weapon::Weapon;
+
+        let mut party = test_party();
+        party.members[1].give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+        let enemy = Combatant::new("Vim".to_string());
+        let hit_rate_before = calculate_hit_rate(&party.members[1], &enemy).unwrap();
+
+        party.members[0].health.damage(10);
+        party.on_member_defeated();
+
+        assert_eq!(-MORALE_LOSS_ON_ALLY_DEFEAT, party.members[1].morale,
+            "An ally's defeat must lower the morale of survivors.");
+        assert_eq!(0, party.members[0].morale,
+            "The defeated member's own morale must not change.");
+        let hit_rate_after = calculate_hit_rate(&party.members[1], &enemy).unwrap();
+        assert!(hit_rate_after < hit_rate_before,
+            "Lower morale must lower the hit rate.");
+    }
+}
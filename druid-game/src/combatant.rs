@@ -1,10 +1,24 @@
 //! This module specifies the [`Combatant`] type, as well as [`CombatStats`] 
 //! for use by it. 
 
+use std::collections::HashMap;
+use std::error::Error;
 use std::fmt::Display;
-use crate::weapon::Weapon;
+use std::ops::Add;
+use crate::combat::Row;
+use crate::element::Element;
+use crate::inventory::Inventory;
+use crate::loot::DropTable;
+use crate::progression::{GrowthCurve, XpTable};
+use crate::spell::Spell;
+use crate::weapon::{OffHand, Weapon, WeaponDatabase};
+
+/// What the damage of the next hit against a [`guarding`](Combatant::guarding) 
+/// combatant is multiplied by.
+pub const GUARD_MULTIPLIER: f64 = 0.5;
 
 /// A representation of a character that might participate in combat. 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Combatant {
     /// The combatant's name, used to refer to them in text.
     pub name: String,
@@ -12,7 +26,50 @@ pub struct Combatant {
     pub stats: CombatStats,
     /// How much damage they can take before being defeated. 
     pub health: Health,
+    /// How many ticks of [`Battle::tick_defeated`] or 
+    /// [`Party::remove_defeated`] the combatant lingers for after being 
+    /// defeated, giving frontends time to play a defeat animation.
+    /// 
+    /// [`Battle::tick_defeated`]: crate::battle::Battle::tick_defeated
+    /// [`Party::remove_defeated`]: crate::combat::Party::remove_defeated
+    pub defeat_timer: u32,
+    defeat_ticks: u32,
+    /// How steady the combatant's nerves are. Morale is added to the hit rate 
+    /// of their attacks, so low morale makes them less accurate. `0` is 
+    /// neutral.
+    pub morale: i32,
+    /// How many action points the combatant regains at the start of each 
+    /// round.
+    pub max_action_points: u32,
+    action_points: u32,
+    /// The most mana the combatant can hold.
+    pub max_mana: i32,
+    mana: i32,
+    /// The spells the combatant knows.
+    pub spells: Vec<Spell>,
+    /// Where the combatant stands in their party's formation.
+    pub row: Row,
+    /// The combatant's elemental alignment, if any, which decides how well 
+    /// they resist elemental attacks.
+    pub element: Option<Element>,
+    /// Temporary buffs and debuffs layered on top of the combatant's stats.
+    pub modifiers: Vec<StatModifier>,
+    /// Whether the combatant is guarding against the next hit, which 
+    /// multiplies its damage by [`GUARD_MULTIPLIER`]. Guarding is used up by 
+    /// the first hit that deals damage, even if it comes rounds later.
+    pub guarding: bool,
+    /// The weapons and items the combatant is carrying.
+    pub inventory: Inventory,
+    /// What the combatant gains each time they level up.
+    pub growth: GrowthCurve,
+    /// How much experience the victors earn for defeating this combatant.
+    pub xp_reward: u32,
+    /// What the combatant may drop when defeated.
+    pub drop_table: DropTable,
+    level: u32,
+    experience: u32,
     current_weapon: Option<Weapon>,
+    off_hand: Option<OffHand>,
 }
 impl Display for Combatant {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -21,7 +78,9 @@ impl Display for Combatant {
 }
 
 impl Combatant {
-    /// Initializes a combatant with health of 10 and default stats of all 0. 
+    /// Initializes a level 1 combatant with health of 10, default stats of 
+    /// all 0, neutral morale, 2 action points per round (enough for one 
+    /// attack), and no mana or spells. 
     /// 
     /// ## Examples
     /// 
@@ -36,8 +95,277 @@ impl Combatant {
             name,
             stats: CombatStats::new(), 
             health: Health::new(10), 
+            defeat_timer: 0,
+            defeat_ticks: 0,
+            morale: 0,
+            max_action_points: 2,
+            action_points: 2,
+            max_mana: 0,
+            mana: 0,
+            spells: Vec::new(),
+            row: Row::Front,
+            element: None,
+            modifiers: Vec::new(),
+            guarding: false,
+            inventory: Inventory::default(),
+            growth: GrowthCurve::default(),
+            xp_reward: 0,
+            drop_table: DropTable::default(),
+            level: 1,
+            experience: 0,
             current_weapon: None, 
+            off_hand: None,
+        }
+    }
+
+    /// Returns the number of action points the combatant has left to spend 
+    /// this round.
+    pub fn action_points(&self) -> u32 {
+        self.action_points
+    }
+
+    /// Restores the combatant's action points to their maximum. Call this at 
+    /// the start of each round.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let mut hero = Combatant::new("Hero of the Week".to_string());
+    /// hero.max_action_points = 3;
+    /// 
+    /// hero.refresh_action_points();
+    /// assert_eq!(3, hero.action_points());
+    /// ```
+    pub fn refresh_action_points(&mut self) {
+        self.action_points = self.max_action_points;
+    }
+
+    /// Spends the given number of action points to pay for an action. If the 
+    /// combatant can't afford the cost, no points are spent and the action 
+    /// should be rejected.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let mut hero = Combatant::new("Hero of the Week".to_string());
+    /// hero.max_action_points = 3;
+    /// hero.refresh_action_points();
+    /// 
+    /// // Attacking costs 2 action points.
+    /// assert!(hero.spend_action_points(2).is_ok());
+    /// assert_eq!(1, hero.action_points());
+    /// 
+    /// // Another attack can't be afforded this round.
+    /// assert!(hero.spend_action_points(2).is_err());
+    /// assert_eq!(1, hero.action_points());
+    /// ```
+    pub fn spend_action_points(&mut self, cost: u32) -> Result<(), NotEnoughActionPoints> {
+        if cost > self.action_points {
+            return Err(NotEnoughActionPoints {
+                cost,
+                available: self.action_points,
+            });
+        }
+        self.action_points -= cost;
+        Ok(())
+    }
+
+    /// Returns the amount of mana the combatant has left.
+    pub fn mana(&self) -> i32 {
+        self.mana
+    }
+
+    /// Restores the given amount of mana, up to the combatant's maximum.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let mut hero = Combatant::new("Hero of the Week".to_string());
+    /// hero.max_mana = 10;
+    /// 
+    /// hero.restore_mana(4);
+    /// assert_eq!(4, hero.mana());
+    /// hero.restore_mana(100);
+    /// assert_eq!(10, hero.mana());
+    /// ```
+    pub fn restore_mana(&mut self, amount: i32) {
+        self.mana = (self.mana + amount).clamp(0, self.max_mana.max(0));
+    }
+
+    /// Spends the given amount of mana to pay for a spell. If the combatant 
+    /// can't afford the cost, no mana is spent and the spell should be 
+    /// rejected.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let mut hero = Combatant::new("Hero of the Week".to_string());
+    /// hero.max_mana = 5;
+    /// hero.restore_mana(5);
+    /// 
+    /// assert!(hero.spend_mana(3).is_ok());
+    /// assert!(hero.spend_mana(3).is_err());
+    /// assert_eq!(2, hero.mana());
+    /// ```
+    pub fn spend_mana(&mut self, cost: i32) -> Result<(), NotEnoughMana> {
+        if cost > self.mana {
+            return Err(NotEnoughMana {
+                cost,
+                available: self.mana,
+            });
+        }
+        self.mana -= cost;
+        Ok(())
+    }
+
+    /// Returns the combatant's level.
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// Returns the total experience the combatant has earned.
+    pub fn experience(&self) -> u32 {
+        self.experience
+    }
+
+    /// Grants experience to the combatant. If this takes them to a new level 
+    /// in the given table, their stats and maximum health grow according to 
+    /// their [`GrowthCurve`] once for each level gained. 
+    /// 
+    /// Returns the number of levels gained. 
+    /// 
+    /// ## Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::progression::XpTable;
+    /// 
+    /// let table = XpTable::new(vec![10, 30]);
+    /// let mut hero = Combatant::new("Hero of the Week".to_string());
+    /// 
+    /// assert_eq!(0, hero.award_xp(5, &table));
+    /// assert_eq!(1, hero.award_xp(5, &table));
+    /// assert_eq!(2, hero.level());
+    /// assert_eq!(1, hero.stats.strength);
+    /// assert_eq!(12, hero.health.max());
+    /// ```
+    pub fn award_xp(&mut self, amount: u32, table: &XpTable) -> u32 {
+        self.experience = self.experience.saturating_add(amount);
+        let new_level = table.level_for(self.experience).max(self.level);
+        let levels_gained = new_level - self.level;
+
+        for _ in 0..levels_gained {
+            self.stats = self.stats.clone() + self.growth.stats.clone();
+            self.health.increase_max(self.growth.health);
+        }
+        self.level = new_level;
+        levels_gained
+    }
+
+    /// Returns the combatant's stats after applying bonuses from their 
+    /// weapon and shield, and from their active 
+    /// [`modifiers`](Combatant::modifiers). These are the stats used in 
+    /// combat calculations.
+    /// 
+    /// ## Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::weapon::Weapon;
+    /// 
+    /// let mut weapon = Weapon::new("Keen Longsword".to_string(), 80, 10);
+    /// weapon.stat_bonus.accuracy = 5;
+    /// 
+    /// let mut wielder = Combatant::new("Hero of the Week".to_string());
+    /// wielder.stats.accuracy = 3;
+    /// wielder.give_weapon(weapon);
+    /// 
+    /// assert_eq!(8, wielder.effective_stats().accuracy);
+    /// assert_eq!(3, wielder.stats.accuracy);
+    /// ```
+    pub fn effective_stats(&self) -> CombatStats {
+        let mut stats = match &self.current_weapon {
+            None => self.stats.clone(),
+            Some(weapon) => self.stats.clone() + weapon.stat_bonus.clone(),
+        };
+        if let Some(OffHand::Shield(shield)) = &self.off_hand {
+            stats = stats + shield.stat_bonus.clone();
+        }
+        for modifier in &self.modifiers {
+            stats = stats + modifier.stats.clone();
+        }
+        stats
+    }
+
+    /// Counts one tick of the combatant's defeat animation, returning whether 
+    /// it finished on this tick, which happens once their 
+    /// [`defeat_timer`](Combatant::defeat_timer) has run out. The countdown 
+    /// starts over if the combatant is healed back above 0 health.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// 
+    /// let mut vim = Combatant::new("Vim".to_string());
+    /// vim.defeat_timer = 1;
+    /// vim.health.damage(10);
+    /// 
+    /// assert!(!vim.tick_defeat());
+    /// assert!(vim.tick_defeat());
+    /// assert!(!vim.tick_defeat(), "The animation only finishes once.");
+    /// ```
+    pub fn tick_defeat(&mut self) -> bool {
+        if self.health.check_status() != HealthStatus::Defeated {
+            self.defeat_ticks = 0;
+            return false;
         }
+        self.defeat_ticks = self.defeat_ticks.saturating_add(1);
+        self.defeat_ticks == self.defeat_timer.saturating_add(1)
+    }
+
+    /// Returns whether the combatant is defeated and their defeat animation 
+    /// has finished, as counted by [`tick_defeat`](Combatant::tick_defeat).
+    pub fn defeat_finished(&self) -> bool {
+        self.health.check_status() == HealthStatus::Defeated && self.defeat_ticks > self.defeat_timer
+    }
+
+    /// Counts down the remaining turns of every active modifier, removing and 
+    /// returning those that have run out. Call this at the end of each round.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::{Combatant, CombatStats, StatModifier};
+    /// 
+    /// let mut hero = Combatant::new("Hero of the Week".to_string());
+    /// let focus = CombatStats { accuracy: 10, ..CombatStats::new() };
+    /// hero.modifiers.push(StatModifier::new("Focus".to_string(), focus, 2));
+    /// assert_eq!(10, hero.effective_stats().accuracy);
+    /// 
+    /// assert!(hero.tick_modifiers().is_empty());
+    /// assert_eq!(10, hero.effective_stats().accuracy);
+    /// 
+    /// let expired = hero.tick_modifiers();
+    /// assert_eq!("Focus", expired[0].name);
+    /// assert_eq!(0, hero.effective_stats().accuracy);
+    /// ```
+    pub fn tick_modifiers(&mut self) -> Vec<StatModifier> {
+        for modifier in &mut self.modifiers {
+            modifier.turns_remaining = modifier.turns_remaining.saturating_sub(1);
+        }
+        let (expired, active) = std::mem::take(&mut self.modifiers).into_iter()
+            .partition(|modifier| modifier.turns_remaining == 0);
+        self.modifiers = active;
+        expired
     }
 
     /// Borrows a reference to the combatant's current weapon.
@@ -62,7 +390,10 @@ impl Combatant {
     }
 
     /// The combatant takes ownership of the given weapon and equips it as 
-    /// their current weapon.
+    /// their current weapon. 
+    /// 
+    /// If the weapon is [`two_handed`](Weapon::two_handed), whatever the 
+    /// combatant held in their off hand is unequipped and returned. 
     /// 
     /// # Examples
     /// 
@@ -77,13 +408,421 @@ impl Combatant {
     /// let mut wielder = Combatant::new("Mysterious Figure".to_string());
     /// wielder.give_weapon(weapon);
     /// ```
-    pub fn give_weapon(&mut self, weapon: Weapon) {
+    /// 
+    /// Two-handed weapons free up the off hand:
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::weapon::{OffHand, Shield, Weapon};
+    /// 
+    /// let mut wielder = Combatant::new("Mysterious Figure".to_string());
+    /// wielder.equip_off_hand(OffHand::Shield(Shield::new("Buckler".to_string(), 2))).unwrap();
+    /// 
+    /// let mut greatsword = Weapon::new("Greatsword".to_string(), 70, 14);
+    /// greatsword.two_handed = true;
+    /// let unequipped = wielder.give_weapon(greatsword);
+    /// assert_eq!("Buckler", unequipped.unwrap().to_string());
+    /// assert!(wielder.off_hand().is_none());
+    /// ```
+    pub fn give_weapon(&mut self, weapon: Weapon) -> Option<OffHand> {
+        let unequipped = match weapon.two_handed {
+            true => self.off_hand.take(),
+            false => None,
+        };
         self.current_weapon = Some(weapon);
+        unequipped
+    }
+
+    /// Borrows a reference to what the combatant holds in their off hand.
+    pub fn off_hand(&self) -> &Option<OffHand> {
+        &self.off_hand
+    }
+
+    /// Borrows a reference to the weapon in the combatant's off hand, if 
+    /// they are dual-wielding.
+    pub fn off_hand_weapon(&self) -> Option<&Weapon> {
+        match &self.off_hand {
+            Some(OffHand::Weapon(weapon)) => Some(weapon),
+            _ => None,
+        }
+    }
+
+    /// Equips a shield or second weapon in the combatant's off hand, 
+    /// returning whatever they held there before. 
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`HandsFull`] if the combatant's current weapon is two-handed, 
+    /// or if the off hand item is itself a two-handed weapon. Nothing is 
+    /// equipped if this happens.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::weapon::{OffHand, Weapon};
+    /// 
+    /// let mut wielder = Combatant::new("Mysterious Figure".to_string());
+    /// wielder.give_weapon(Weapon::new("Shortsword".to_string(), 70, 6));
+    /// wielder.equip_off_hand(OffHand::Weapon(Weapon::new("Dagger".to_string(), 80, 4))).unwrap();
+    /// assert_eq!("Dagger", wielder.off_hand_weapon().unwrap().name);
+    /// 
+    /// let mut greatsword = Weapon::new("Greatsword".to_string(), 70, 14);
+    /// greatsword.two_handed = true;
+    /// assert!(wielder.equip_off_hand(OffHand::Weapon(greatsword)).is_err());
+    /// ```
+    pub fn equip_off_hand(&mut self, off_hand: OffHand) -> Result<Option<OffHand>, HandsFull> {
+        let main_two_handed = self.current_weapon.as_ref().is_some_and(|weapon| weapon.two_handed);
+        let off_two_handed = matches!(&off_hand, OffHand::Weapon(weapon) if weapon.two_handed);
+        if main_two_handed || off_two_handed {
+            return Err(HandsFull);
+        }
+        Ok(self.off_hand.replace(off_hand))
+    }
+
+    /// Unequips and returns whatever the combatant holds in their off hand.
+    pub fn take_off_hand(&mut self) -> Option<OffHand> {
+        self.off_hand.take()
+    }
+
+    /// Equips the weapon at the given index of the combatant's inventory. 
+    /// The previously equipped weapon, if any, takes its place in the 
+    /// inventory. 
+    /// 
+    /// Returns the newly equipped weapon, or [`Option::None`] if there is no 
+    /// weapon at that index, or if it is two-handed while the combatant holds 
+    /// something in their off hand.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Combatant;
+    /// use druid_game::weapon::Weapon;
+    /// 
+    /// let mut wielder = Combatant::new("Mysterious Figure".to_string());
+    /// wielder.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+    /// wielder.inventory.add_weapon(Weapon::new("Club".to_string(), 60, 6)).unwrap();
+    /// 
+    /// wielder.equip_from_inventory(0);
+    /// assert_eq!("Club", wielder.current_weapon().as_ref().unwrap().name);
+    /// assert_eq!("Longsword", wielder.inventory.weapons()[0].name);
+    /// ```
+    pub fn equip_from_inventory(&mut self, index: usize) -> Option<&Weapon> {
+        let weapon = self.inventory.weapons().get(index)?;
+        if weapon.two_handed && self.off_hand.is_some() {
+            return None;
+        }
+
+        let weapon = match self.current_weapon.take() {
+            None => self.inventory.remove_weapon(index),
+            Some(previous) => self.inventory.replace_weapon(index, previous),
+        };
+        self.current_weapon = weapon;
+        self.current_weapon.as_ref()
     }
 }
 
-/// A set of stats used in calculating combat values.
+/// The style of suffix a [`NameGenerator`] appends to tell combatants with 
+/// the same name apart.
+#[derive(Default, PartialEq, Debug, Clone, Copy)]
+pub enum SuffixStyle {
+    /// Letters, as in "Goblin A", "Goblin B", ..., "Goblin Z", "Goblin AA".
+    #[default]
+    Letters,
+    /// Numbers starting from 1, as in "Goblin 1", "Goblin 2".
+    Numbers,
+}
+
+/// Hands out distinct names for combatants spawned from the same base name, 
+/// so they can be told apart in text.
+/// 
+/// Names handed out one at a time by [`next_name`](NameGenerator::next_name) 
+/// always get a suffix, since the generator can't know whether a duplicate 
+/// will be spawned later, and a name already shown can't be changed. Use 
+/// [`name_all`](NameGenerator::name_all) to name a whole group at once, 
+/// which only suffixes the duplicates. Suffixes count separately for each 
+/// base name, and the same sequence of requests always produces the same 
+/// names.
 #[derive(Default)]
+pub struct NameGenerator {
+    /// The style of suffix to append.
+    pub style: SuffixStyle,
+    counts: HashMap<String, usize>,
+}
+
+impl NameGenerator {
+    /// Initializes a name generator using the given suffix style.
+    pub fn new(style: SuffixStyle) -> NameGenerator {
+        NameGenerator {
+            style,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Returns the next distinct name for the given base name.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::{NameGenerator, SuffixStyle};
+    /// 
+    /// let mut names = NameGenerator::new(SuffixStyle::Letters);
+    /// assert_eq!("Goblin A", names.next_name("Goblin"));
+    /// assert_eq!("Goblin B", names.next_name("Goblin"));
+    /// assert_eq!("Slime A", names.next_name("Slime"));
+    /// 
+    /// let mut names = NameGenerator::new(SuffixStyle::Numbers);
+    /// assert_eq!("Goblin 1", names.next_name("Goblin"));
+    /// ```
+    pub fn next_name(&mut self, base_name: &str) -> String {
+        let count = self.counts.entry(base_name.to_string()).or_insert(0);
+        let index = *count;
+        *count += 1;
+
+        let suffix = match self.style {
+            SuffixStyle::Letters => letter_suffix(index),
+            SuffixStyle::Numbers => (index + 1).to_string(),
+        };
+        format!("{base_name} {suffix}")
+    }
+
+    /// Returns distinct names for a group of combatants spawned together. 
+    /// Base names that appear only once, and weren't handed out before, are 
+    /// left as they are; the rest are suffixed like 
+    /// [`next_name`](NameGenerator::next_name).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::NameGenerator;
+    /// 
+    /// let mut names = NameGenerator::default();
+    /// assert_eq!(vec!["Goblin A", "Slime", "Goblin B"], names.name_all(&["Goblin", "Slime", "Goblin"]));
+    /// assert_eq!(vec!["Slime B"], names.name_all(&["Slime"]));
+    /// ```
+    pub fn name_all(&mut self, base_names: &[&str]) -> Vec<String> {
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        for base_name in base_names {
+            *occurrences.entry(base_name).or_insert(0) += 1;
+        }
+
+        base_names.iter()
+            .map(|base_name| {
+                if occurrences[base_name] == 1 && !self.counts.contains_key(*base_name) {
+                    // Later duplicates still need a suffix to stay distinct.
+                    self.counts.insert(base_name.to_string(), 1);
+                    base_name.to_string()
+                }
+                else {
+                    self.next_name(base_name)
+                }
+            })
+            .collect()
+    }
+
+    /// Initializes a combatant with the next distinct name for the given 
+    /// base name.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::NameGenerator;
+    /// 
+    /// let mut names = NameGenerator::default();
+    /// let goblin = names.spawn("Goblin");
+    /// assert_eq!("Goblin A", goblin.name);
+    /// ```
+    pub fn spawn(&mut self, base_name: &str) -> Combatant {
+        Combatant::new(self.next_name(base_name))
+    }
+}
+
+/// Converts a zero-based index to a letter suffix: A through Z, then AA, AB, 
+/// and so on.
+fn letter_suffix(index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = index + 1;
+    while remaining > 0 {
+        remaining -= 1;
+        letters.push(b'A' + (remaining % 26) as u8);
+        remaining /= 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).expect("Suffix letters must be ASCII")
+}
+
+/// A blueprint for combatants, such as a kind of enemy, that can be defined 
+/// in data files instead of in code.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CombatantTemplate {
+    /// The base name of combatants spawned from this template.
+    pub name: String,
+    /// The stats combatants start with.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stats: CombatStats,
+    /// The maximum health combatants start with.
+    pub max_health: i32,
+    /// The name of the weapon combatants start with equipped, if any, as 
+    /// found in a [`WeaponDatabase`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weapon: Option<String>,
+    /// How much experience the victors earn for defeating a combatant 
+    /// spawned from this template.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub xp_reward: u32,
+    /// The maximum mana combatants start with. They start with full mana.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_mana: i32,
+    /// The spells combatants know.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spells: Vec<Spell>,
+    /// The elemental alignment of combatants, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub element: Option<Element>,
+    /// What combatants may drop when defeated.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub drop_table: DropTable,
+}
+
+impl CombatantTemplate {
+    /// Loads a template from a JSON object. Stats, weapon and experience 
+    /// reward may be left out.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the text isn't valid JSON, or doesn't describe a 
+    /// template.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::CombatantTemplate;
+    /// 
+    /// let json = r#"{ "name": "Goblin", "max_health": 8, "weapon": "Club" }"#;
+    /// let goblin = CombatantTemplate::from_json(json).unwrap();
+    /// assert_eq!(Some("Club".to_string()), goblin.weapon);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<CombatantTemplate, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Initializes a combatant from the template, named by the given name 
+    /// generator and armed from the given weapon database.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an [`UnknownWeapon`] error if the template's weapon isn't in 
+    /// the database.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::{CombatantTemplate, CombatStats, NameGenerator};
+    /// use druid_game::weapon::{Weapon, WeaponDatabase};
+    /// 
+    /// let weapons = WeaponDatabase::new(vec![Weapon::new("Club".to_string(), 60, 6)]);
+    /// let template = CombatantTemplate {
+    ///     name: "Goblin".to_string(),
+    ///     stats: CombatStats::new(),
+    ///     max_health: 8,
+    ///     weapon: Some("Club".to_string()),
+    ///     xp_reward: 5,
+    ///     max_mana: 0,
+    ///     spells: Vec::new(),
+    ///     element: None,
+    ///     drop_table: Default::default(),
+    /// };
+    /// 
+    /// let mut names = NameGenerator::default();
+    /// let goblin = template.spawn(&mut names, &weapons).unwrap();
+    /// assert_eq!("Goblin A", goblin.name);
+    /// assert_eq!(8, goblin.health.max());
+    /// assert_eq!("Club", goblin.current_weapon().as_ref().unwrap().name);
+    /// ```
+    pub fn spawn(&self, names: &mut NameGenerator, weapons: &WeaponDatabase) -> Result<Combatant, UnknownWeapon> {
+        let weapon = match &self.weapon {
+            Some(name) => Some(weapons.get(name)
+                .ok_or_else(|| UnknownWeapon(name.clone()))?
+                .clone()),
+            None => None,
+        };
+
+        let mut combatant = names.spawn(&self.name);
+        combatant.stats = self.stats.clone();
+        combatant.health = Health::new(self.max_health);
+        combatant.xp_reward = self.xp_reward;
+        combatant.max_mana = self.max_mana;
+        combatant.restore_mana(self.max_mana);
+        combatant.spells = self.spells.clone();
+        combatant.element = self.element;
+        combatant.drop_table = self.drop_table.clone();
+        combatant.current_weapon = weapon;
+        Ok(combatant)
+    }
+}
+
+/// An error returned when a weapon is looked up by a name that no weapon 
+/// has.
+#[derive(PartialEq, Debug)]
+pub struct UnknownWeapon(pub String);
+impl Display for UnknownWeapon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no weapon named '{0}'", self.0)
+    }
+}
+impl Error for UnknownWeapon {}
+
+/// An error returned when a combatant tries to take an action they don't 
+/// have enough action points for.
+#[derive(PartialEq, Debug)]
+pub struct NotEnoughActionPoints {
+    /// The cost of the attempted action.
+    pub cost: u32,
+    /// The action points the combatant had left.
+    pub available: u32,
+}
+impl Display for NotEnoughActionPoints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "action costs {0} action points, but only {1} remain", 
+            self.cost, self.available)
+    }
+}
+impl Error for NotEnoughActionPoints {}
+
+/// An error returned when a combatant tries to hold something in their off 
+/// hand while their hands are taken by a two-handed weapon.
+#[derive(PartialEq, Debug)]
+pub struct HandsFull;
+impl Display for HandsFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("two-handed weapons leave no hand free")
+    }
+}
+impl Error for HandsFull {}
+
+/// An error returned when a combatant tries to cast a spell they don't have 
+/// enough mana for.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct NotEnoughMana {
+    /// The mana cost of the attempted spell.
+    pub cost: i32,
+    /// The mana the combatant had left.
+    pub available: i32,
+}
+impl Display for NotEnoughMana {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "spell costs {0} mana, but only {1} remains", 
+            self.cost, self.available)
+    }
+}
+impl Error for NotEnoughMana {}
+
+/// A set of stats used in calculating combat values.
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CombatStats {
     /// Affects how likely they are to direct-hit with an attack.
     pub accuracy: i32,
@@ -93,6 +832,8 @@ pub struct CombatStats {
     pub strength: i32,
     /// Affects how much they can reduce the damage they take.
     pub defense: i32,
+    /// Affects how early they act in each round.
+    pub speed: i32,
 }
 
 impl CombatStats {
@@ -103,12 +844,52 @@ impl CombatStats {
             evasion: 0,
             strength: 0,
             defense: 0,
+            speed: 0,
+        }
+    }
+}
+
+impl Add for CombatStats {
+    type Output = CombatStats;
+
+    fn add(self, rhs: CombatStats) -> CombatStats {
+        CombatStats {
+            accuracy: self.accuracy + rhs.accuracy,
+            evasion: self.evasion + rhs.evasion,
+            strength: self.strength + rhs.strength,
+            defense: self.defense + rhs.defense,
+            speed: self.speed + rhs.speed,
+        }
+    }
+}
+
+/// A temporary change to a combatant's stats, such as a buff or debuff, that 
+/// lasts for a number of turns.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatModifier {
+    /// The name used to refer to the modifier in text.
+    pub name: String,
+    /// The stats added to the combatant's own. Negative values make a 
+    /// debuff.
+    pub stats: CombatStats,
+    /// How many more rounds the modifier lasts.
+    pub turns_remaining: u32,
+}
+
+impl StatModifier {
+    /// Constructs a modifier that lasts for the given number of rounds.
+    pub fn new(name: String, stats: CombatStats, turns: u32) -> StatModifier {
+        StatModifier {
+            name,
+            stats,
+            turns_remaining: turns,
         }
     }
 }
 
 /// Enum specifying general health states.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum HealthStatus {
     /// The subject has its maximum health.
     Healthy,
@@ -123,6 +904,8 @@ pub enum HealthStatus {
 /// Health is bound between `0` and a maximum value, which can be manipulated. 
 /// Most functions which alter health also return a [`HealthStatus`] to gauge 
 /// current health relative to the maxiumum. 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Health {
     current: i32,
     max: i32,
@@ -197,11 +980,83 @@ impl Health {
     /// assert_eq!(0, health.current());
     /// ```
     pub fn damage(&mut self, damage: i32) -> HealthStatus {
+        self.apply_damage(damage).status
+    }
+
+    /// Reduces the current health by the given damage, then returns a 
+    /// [`DamageReport`] describing the new health status and any damage 
+    /// beyond what was needed to reach 0.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Health;
+    /// use druid_game::combatant::HealthStatus;
+    /// 
+    /// let mut health = Health::new(10);
+    /// 
+    /// let report = health.apply_damage(15);
+    /// assert_eq!(HealthStatus::Defeated, report.status);
+    /// assert_eq!(5, report.overkill);
+    /// assert!(!report.is_exact_kill());
+    /// ```
+    pub fn apply_damage(&mut self, damage: i32) -> DamageReport {
+        let overkill = (damage - self.current).max(0);
         self.current -= damage;
         self.clamp();
+        DamageReport {
+            status: self.check_status(),
+            overkill,
+        }
+    }
+
+    /// Increases the current health by the given amount, up to the maximum, 
+    /// then returns the current health status.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Health;
+    /// use druid_game::combatant::HealthStatus;
+    /// 
+    /// let mut health = Health::new(10);
+    /// health.damage(7);
+    /// 
+    /// let new_status = health.heal(5);
+    /// assert_eq!(HealthStatus::Hurt, new_status);
+    /// assert_eq!(8, health.current());
+    /// 
+    /// let new_status = health.heal(5);
+    /// assert_eq!(HealthStatus::Healthy, new_status);
+    /// assert_eq!(10, health.current());
+    /// ```
+    pub fn heal(&mut self, amount: i32) -> HealthStatus {
+        self.current += amount;
+        self.clamp();
         self.check_status()
     }
 
+    /// Increases the maximum health by the given amount, and current health 
+    /// along with it.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Health;
+    /// 
+    /// let mut health = Health::new(10);
+    /// health.damage(4);
+    /// 
+    /// health.increase_max(5);
+    /// assert_eq!(15, health.max());
+    /// assert_eq!(11, health.current());
+    /// ```
+    pub fn increase_max(&mut self, amount: i32) {
+        self.max += amount;
+        self.current += amount;
+        self.clamp();
+    }
+
     /// Clamps current health to the range of `0..max` inclusive. 
     /// 
     /// Must call every time current health is changed.
@@ -239,9 +1094,40 @@ impl Health {
     }
 }
 
+/// The outcome of applying damage to a [`Health`].
+#[derive(PartialEq, Debug)]
+pub struct DamageReport {
+    /// The health status after the damage was applied.
+    pub status: HealthStatus,
+    /// How much of the damage exceeded the health that remained. This is 
+    /// always `0` unless the damage defeated its target. 
+    pub overkill: i32,
+}
+
+impl DamageReport {
+    /// Returns `true` if the damage defeated its target with nothing to 
+    /// spare.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::combatant::Health;
+    /// 
+    /// let mut health = Health::new(10);
+    /// 
+    /// let report = health.apply_damage(10);
+    /// assert!(report.is_exact_kill());
+    /// ```
+    pub fn is_exact_kill(&self) -> bool {
+        self.status == HealthStatus::Defeated && self.overkill == 0
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::battle::calculate_hit_rate;
+    use crate::weapon::Shield;
 
     #[test]
     fn test_healthy_status() {
@@ -271,4 +1157,239 @@ mod test {
         assert_eq!(HealthStatus::Defeated, actual,
             "Health status must be defeated after reducing health to 0.");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_overkill() {
+        let mut health = Health::new(10);
+
+        let report = health.apply_damage(15);
+        assert_eq!(5, report.overkill,
+            "Overkill must be the damage beyond the remaining health.");
+        assert!(!report.is_exact_kill());
+    }
+
+    #[test]
+    fn test_exact_kill() {
+        let mut health = Health::new(10);
+
+        let report = health.apply_damage(10);
+        assert_eq!(0, report.overkill,
+            "A precise killing blow must not report overkill.");
+        assert!(report.is_exact_kill());
+    }
+
+    #[test]
+    fn test_no_overkill_when_surviving() {
+        let mut health = Health::new(10);
+
+        let report = health.apply_damage(4);
+        assert_eq!(HealthStatus::Hurt, report.status);
+        assert_eq!(0, report.overkill);
+        assert!(!report.is_exact_kill());
+    }
+
+    #[test]
+    fn test_action_point_budget() {
+        let mut combatant = Combatant::new("Alice".to_string());
+        combatant.max_action_points = 3;
+        combatant.refresh_action_points();
+
+        assert!(combatant.spend_action_points(1).is_ok());
+        assert!(combatant.spend_action_points(1).is_ok());
+        assert_eq!(Err(NotEnoughActionPoints { cost: 2, available: 1 }),
+            combatant.spend_action_points(2),
+            "A 2-cost action must fail with 1 action point left.");
+        assert!(combatant.spend_action_points(1).is_ok());
+        assert!(combatant.spend_action_points(1).is_err(),
+            "No action can be taken after all action points are spent.");
+    }
+
+    #[test]
+    fn test_refresh_action_points() {
+        let mut combatant = Combatant::new("Alice".to_string());
+        combatant.max_action_points = 2;
+        combatant.refresh_action_points();
+        combatant.spend_action_points(2).unwrap();
+
+        combatant.refresh_action_points();
+        assert_eq!(2, combatant.action_points(),
+            "Action points must be restored to the maximum each round.");
+    }
+
+    #[test]
+    fn test_spawned_names_are_distinct() {
+        let mut names = NameGenerator::default();
+        let goblins: Vec<Combatant> = (0..3).map(|_| names.spawn("Goblin")).collect();
+
+        let names: Vec<&str> = goblins.iter().map(|goblin| goblin.name.as_str()).collect();
+        assert_eq!(vec!["Goblin A", "Goblin B", "Goblin C"], names,
+            "Spawned duplicates must have distinct display names.");
+    }
+
+    #[test]
+    fn test_name_all_only_suffixes_duplicates() {
+        let mut names = NameGenerator::new(SuffixStyle::Numbers);
+        assert_eq!(vec!["Slime", "Goblin 1", "Goblin 2"], names.name_all(&["Slime", "Goblin", "Goblin"]),
+            "Only duplicated base names must get a suffix.");
+        assert_eq!("Slime 2", names.next_name("Slime"),
+            "Later names must not repeat an unsuffixed one.");
+    }
+
+    #[test]
+    fn test_letter_suffix_rolls_over() {
+        assert_eq!("Z", letter_suffix(25));
+        assert_eq!("AA", letter_suffix(26));
+        assert_eq!("AZ", letter_suffix(51));
+        assert_eq!("BA", letter_suffix(52));
+    }
+
+    #[test]
+    fn test_effective_stats_include_weapon_bonus() {
+        let mut weapon = Weapon::new("Keen Longsword".to_string(), 80, 10);
+        weapon.stat_bonus.accuracy = 5;
+        weapon.stat_bonus.defense = 2;
+        let mut combatant = Combatant::new("Alice".to_string());
+        combatant.stats.accuracy = 1;
+        assert_eq!(combatant.stats, combatant.effective_stats(),
+            "Effective stats must match base stats without a weapon.");
+
+        combatant.give_weapon(weapon);
+        let effective = combatant.effective_stats();
+        assert_eq!(6, effective.accuracy,
+            "Effective stats must include the weapon's bonus.");
+        assert_eq!(2, effective.defense);
+        assert_eq!(1, combatant.stats.accuracy,
+            "Weapon bonuses must not change base stats.");
+
+        let defender = Combatant::new("Vim".to_string());
+        let bonused = calculate_hit_rate(&combatant, &defender);
+        combatant.give_weapon(Weapon::new("Longsword".to_string(), 80, 10));
+        let unbonused = calculate_hit_rate(&combatant, &defender);
+        assert_eq!(Some(86), bonused,
+            "Hit rate must include the weapon's accuracy bonus.");
+        assert_eq!(Some(81), unbonused);
+    }
+
+    #[test]
+    fn test_two_handed_blocks_off_hand() {
+        let mut greatsword = Weapon::new("Greatsword".to_string(), 70, 14);
+        greatsword.two_handed = true;
+        let mut combatant = Combatant::new("Combatant".to_string());
+        combatant.inventory.add_weapon(greatsword.clone()).unwrap();
+        combatant.equip_off_hand(OffHand::Shield(Shield::new("Buckler".to_string(), 2))).unwrap();
+        assert_eq!(2, combatant.effective_stats().defense,
+            "Shields must add their stat bonus.");
+
+        assert!(combatant.equip_from_inventory(0).is_none(),
+            "Two-handed weapons must not be equipped while the off hand is in use.");
+        combatant.take_off_hand();
+        assert!(combatant.equip_from_inventory(0).is_some());
+        assert_eq!(Err(HandsFull), combatant.equip_off_hand(OffHand::Shield(Shield::new("Buckler".to_string(), 2))),
+            "Two-handed weapons must block the off hand.");
+    }
+
+    #[test]
+    fn test_equip_from_inventory() {
+        let mut combatant = Combatant::new("Alice".to_string());
+        combatant.inventory.add_weapon(Weapon::new("Club".to_string(), 60, 6)).unwrap();
+
+        assert!(combatant.equip_from_inventory(1).is_none(),
+            "Equipping from an empty slot must fail.");
+        assert_eq!("Club", combatant.equip_from_inventory(0).unwrap().name);
+        assert!(combatant.inventory.is_empty(),
+            "Equipping with empty hands must take the weapon out of the inventory.");
+
+        combatant.inventory.add_weapon(Weapon::new("Longsword".to_string(), 70, 8)).unwrap();
+        assert!(combatant.equip_from_inventory(3).is_none());
+        assert_eq!("Club", combatant.current_weapon().as_ref().unwrap().name,
+            "A failed swap must keep the current weapon equipped.");
+
+        assert_eq!("Longsword", combatant.equip_from_inventory(0).unwrap().name);
+        assert_eq!("Club", combatant.inventory.weapons()[0].name,
+            "Swapping must stow the previous weapon.");
+    }
+
+    #[test]
+    fn test_award_xp_multiple_levels() {
+        let table = XpTable::new(vec![10, 20, 30]);
+        let mut combatant = Combatant::new("Alice".to_string());
+
+        let levels_gained = combatant.award_xp(25, &table);
+        assert_eq!(2, levels_gained);
+        assert_eq!(3, combatant.level());
+        assert_eq!(2, combatant.stats.accuracy,
+            "Stats must grow once for each level gained.");
+        assert_eq!(14, combatant.health.max());
+    }
+
+    #[test]
+    fn test_award_xp_at_max_level() {
+        let table = XpTable::new(vec![10]);
+        let mut combatant = Combatant::new("Alice".to_string());
+        combatant.award_xp(10, &table);
+
+        assert_eq!(0, combatant.award_xp(1000, &table),
+            "No levels can be gained past the maximum level.");
+        assert_eq!(2, combatant.level());
+        assert_eq!(1010, combatant.experience());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut combatant = Combatant::new("Alice".to_string());
+        combatant.stats.speed = 3;
+        combatant.health.damage(4);
+        combatant.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
+        combatant.award_xp(15, &XpTable::new(vec![10]));
+
+        let json = serde_json::to_string(&combatant).unwrap();
+        let loaded: Combatant = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(combatant.name, loaded.name);
+        assert_eq!(combatant.stats, loaded.stats);
+        assert_eq!(combatant.health.current(), loaded.health.current(),
+            "Current health must survive a round trip.");
+        assert_eq!(combatant.current_weapon(), loaded.current_weapon());
+        assert_eq!(2, loaded.level(),
+            "Private progression fields must survive a round trip.");
+        assert_eq!(15, loaded.experience());
+    }
+
+    #[test]
+    fn test_spawn_template_unknown_weapon() {
+        let template = CombatantTemplate {
+            name: "Goblin".to_string(),
+            stats: CombatStats::new(),
+            max_health: 8,
+            weapon: Some("Axe".to_string()),
+            xp_reward: 0,
+            max_mana: 0,
+            spells: Vec::new(),
+            element: None,
+            drop_table: DropTable::default(),
+        };
+        let mut names = NameGenerator::default();
+
+        assert_eq!(Some(UnknownWeapon("Axe".to_string())),
+            template.spawn(&mut names, &WeaponDatabase::default()).err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_template_from_json() {
+        let weapons = WeaponDatabase::from_json(
+            r#"[{ "name": "Club", "hit_rate": 60, "damage": 6 }]"#).unwrap();
+        let template = CombatantTemplate::from_json(
+            r#"{ "name": "Goblin", "max_health": 8, "stats": { "speed": 2 }, "weapon": "club" }"#).unwrap();
+
+        let mut names = NameGenerator::default();
+        let goblin = template.spawn(&mut names, &weapons).unwrap();
+        assert_eq!(2, goblin.stats.speed);
+        assert_eq!(0, goblin.stats.strength,
+            "Stats left out of the data must default to 0.");
+        assert_eq!(Some(&Weapon::new("Club".to_string(), 60, 6)), goblin.current_weapon().as_ref());
+        assert!(CombatantTemplate::from_json(r#"{ "name": "Goblin" }"#).is_err(),
+            "Maximum health must be required.");
+    }
+}
@@ -0,0 +1,251 @@
+//! This module specifies the developer [`Console`], which applies text
+//! commands to the game state. It is only available with the `debug` feature.
+
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::combatant::Combatant;
+use crate::weapon::Weapon;
+
+/// A list of reasons a console command can fail.
+#[derive(PartialEq, Debug)]
+pub enum ConsoleError {
+    /// The command's name wasn't recognized.
+    UnknownCommand(String),
+    /// No combatant has the given name.
+    UnknownCombatant(String),
+    /// No weapon in the console's armory has the given name.
+    UnknownWeapon(String),
+    /// No stat has the given name.
+    UnknownStat(String),
+    /// The command was given the wrong number or kind of arguments. Contains
+    /// the expected usage.
+    BadArguments(String),
+}
+impl Display for ConsoleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsoleError::UnknownCommand(name) => write!(f, "Unknown command '{name}'"),
+            ConsoleError::UnknownCombatant(name) => write!(f, "No combatant named '{name}'"),
+            ConsoleError::UnknownWeapon(name) => write!(f, "No weapon named '{name}'"),
+            ConsoleError::UnknownStat(name) => write!(f, "No stat named '{name}'"),
+            ConsoleError::BadArguments(usage) => write!(f, "Usage: {usage}"),
+        }
+    }
+}
+impl Error for ConsoleError {}
+
+/// A developer console that parses text commands and applies them to a set
+/// of combatants.
+///
+/// The following commands are supported. Commands and names are matched
+/// without regard to case.
+///
+/// - `heal <combatant> <amount>`
+/// - `damage <combatant> <amount>`
+/// - `give <combatant> <weapon>`, where the weapon's name is the rest of the
+///   line, so it may contain spaces
+/// - `setstat <combatant> <acc|eva|str|def|spd> <value>`
+#[derive(Default)]
+pub struct Console {
+    /// The weapons that can be handed out with the `give` command.
+    pub armory: Vec<Weapon>,
+}
+
+impl Console {
+    /// Initializes a console that can hand out the given weapons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::debug::Console;
+    /// use druid_game::weapon::Weapon;
+    ///
+    /// let console = Console::new(vec![
+    ///     Weapon::new("Longsword".to_string(), 70, 8),
+    /// ]);
+    /// ```
+    pub fn new(armory: Vec<Weapon>) -> Console {
+        Console { armory }
+    }
+
+    /// Parses and applies a single command, returning a message describing
+    /// what changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::debug::Console;
+    /// use druid_game::combatant::Combatant;
+    ///
+    /// let console = Console::default();
+    /// let mut combatants = vec![Combatant::new("Alice".to_string())];
+    ///
+    /// let message = console.execute("setstat alice str 10", &mut combatants);
+    /// assert!(message.is_ok());
+    /// assert_eq!(10, combatants[0].stats.strength);
+    ///
+    /// let message = console.execute("dance alice", &mut combatants);
+    /// assert!(message.is_err());
+    /// ```
+    pub fn execute(&self, command: &str, combatants: &mut [Combatant]) -> Result<String, ConsoleError> {
+        let args: Vec<&str> = command.split_whitespace().collect();
+        let Some((verb, args)) = args.split_first() else {
+            return Err(ConsoleError::UnknownCommand(String::new()));
+        };
+        match (verb.to_ascii_lowercase().as_str(), args) {
+            ("heal", [name, amount]) => {
+                let amount = parse_number(amount, "heal <combatant> <amount>")?;
+                let target = find_combatant(combatants, name)?;
+                target.health.heal(amount);
+                Ok(format!("{target} has {0} hit points.", target.health.current()))
+            },
+            ("damage", [name, amount]) => {
+                let amount = parse_number(amount, "damage <combatant> <amount>")?;
+                let target = find_combatant(combatants, name)?;
+                target.health.damage(amount);
+                Ok(format!("{target} has {0} hit points.", target.health.current()))
+            },
+            ("give", [name, weapon_name @ ..]) if !weapon_name.is_empty() => {
+                let weapon_name = weapon_name.join(" ");
+                let weapon = self.armory.iter()
+                    .find(|weapon| weapon.name.eq_ignore_ascii_case(&weapon_name))
+                    .ok_or(ConsoleError::UnknownWeapon(weapon_name))?;
+                let target = find_combatant(combatants, name)?;
+                target.give_weapon(weapon.clone());
+                Ok(format!("{target} equipped {weapon}."))
+            },
+            ("setstat", [name, stat, value]) => {
+                let value = parse_number(value, "setstat <combatant> <stat> <value>")?;
+                let target = find_combatant(combatants, name)?;
+                let stat = match stat.to_ascii_lowercase().as_str() {
+                    "acc" | "accuracy" => &mut target.stats.accuracy,
+                    "eva" | "evasion" => &mut target.stats.evasion,
+                    "str" | "strength" => &mut target.stats.strength,
+                    "def" | "defense" => &mut target.stats.defense,
+                    "spd" | "speed" => &mut target.stats.speed,
+                    _ => return Err(ConsoleError::UnknownStat(stat.to_string())),
+                };
+                *stat = value;
+                Ok(format!("Set {target}'s {0} to {value}.", args[1]))
+            },
+            ("heal", _) => Err(ConsoleError::BadArguments("heal <combatant> <amount>".to_string())),
+            ("damage", _) => Err(ConsoleError::BadArguments("damage <combatant> <amount>".to_string())),
+            ("give", _) => Err(ConsoleError::BadArguments("give <combatant> <weapon>".to_string())),
+            ("setstat", _) => Err(ConsoleError::BadArguments("setstat <combatant> <stat> <value>".to_string())),
+            _ => Err(ConsoleError::UnknownCommand(verb.to_string())),
+        }
+    }
+}
+
+fn find_combatant<'a>(combatants: &'a mut [Combatant], name: &str) -> Result<&'a mut Combatant, ConsoleError> {
+    combatants.iter_mut()
+        .find(|combatant| combatant.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| ConsoleError::UnknownCombatant(name.to_string()))
+}
+
+fn parse_number(text: &str, usage: &str) -> Result<i32, ConsoleError> {
+    text.parse().map_err(|_| ConsoleError::BadArguments(usage.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn setup() -> (Console, Vec<Combatant>) {
+        let console = Console::new(vec![
+            Weapon::new("Longsword".to_string(), 70, 8),
+            Weapon::new("Keen Longsword".to_string(), 80, 10),
+        ]);
+        let combatants = vec![
+            Combatant::new("Alice".to_string()),
+            Combatant::new("Vim".to_string()),
+        ];
+        (console, combatants)
+    }
+
+    #[test]
+    fn test_heal() {
+        let (console, mut combatants) = setup();
+        combatants[0].health.damage(8);
+
+        let result = console.execute("heal alice 5", &mut combatants);
+        assert!(result.is_ok());
+        assert_eq!(7, combatants[0].health.current(),
+            "Heal command must restore the given amount of health.");
+    }
+
+    #[test]
+    fn test_damage() {
+        let (console, mut combatants) = setup();
+
+        let result = console.execute("damage vim 3", &mut combatants);
+        assert!(result.is_ok());
+        assert_eq!(7, combatants[1].health.current(),
+            "Damage command must remove the given amount of health.");
+    }
+
+    #[test]
+    fn test_give() {
+        let (console, mut combatants) = setup();
+
+        let result = console.execute("give vim longsword", &mut combatants);
+        assert!(result.is_ok());
+        let weapon = combatants[1].current_weapon().as_ref()
+            .expect("Give command must equip the weapon.");
+        assert_eq!("Longsword", weapon.name);
+
+        let result = console.execute("give vim keen  longsword", &mut combatants);
+        assert!(result.is_ok());
+        assert_eq!("Keen Longsword", combatants[1].current_weapon().as_ref().unwrap().name,
+            "Give command must take the rest of the line as the weapon's name.");
+    }
+
+    #[test]
+    fn test_commands_ignore_case() {
+        let (console, mut combatants) = setup();
+
+        assert!(console.execute("DAMAGE Vim 3", &mut combatants).is_ok());
+        assert!(console.execute("Heal vim 1", &mut combatants).is_ok());
+        assert_eq!(8, combatants[1].health.current(),
+            "Commands must be recognized regardless of case.");
+    }
+
+    #[test]
+    fn test_setstat() {
+        let (console, mut combatants) = setup();
+
+        for (stat, value) in [("acc", 1), ("eva", 2), ("str", 3), ("def", 4), ("spd", 5)] {
+            let command = format!("setstat alice {stat} {value}");
+            assert!(console.execute(&command, &mut combatants).is_ok());
+        }
+        let stats = &combatants[0].stats;
+        assert_eq!((1, 2, 3, 4, 5),
+            (stats.accuracy, stats.evasion, stats.strength, stats.defense, stats.speed),
+            "Setstat command must set each named stat.");
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        let (console, mut combatants) = setup();
+
+        let result = console.execute("dance alice", &mut combatants);
+        assert_eq!(Err(ConsoleError::UnknownCommand("dance".to_string())), result);
+    }
+
+    #[test]
+    fn test_bad_arguments() {
+        let (console, mut combatants) = setup();
+
+        assert!(matches!(console.execute("heal alice", &mut combatants),
+            Err(ConsoleError::BadArguments(_))));
+        assert!(matches!(console.execute("heal alice lots", &mut combatants),
+            Err(ConsoleError::BadArguments(_))));
+        assert_eq!(Err(ConsoleError::UnknownCombatant("bob".to_string())),
+            console.execute("heal bob 5", &mut combatants));
+        assert_eq!(Err(ConsoleError::UnknownWeapon("axe".to_string())),
+            console.execute("give vim axe", &mut combatants));
+        assert_eq!(Err(ConsoleError::UnknownStat("luck".to_string())),
+            console.execute("setstat vim luck 5", &mut combatants));
+    }
+}
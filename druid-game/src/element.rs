@@ -0,0 +1,109 @@
+//! This module specifies the [`Element`]s that weapons, spells and
+//! combatants can be aligned with, and the affinity chart that decides how
+//! effective they are against each other.
+
+use std::fmt::Display;
+
+/// An elemental alignment. Each element is strong against one other element,
+/// and weak against the remaining one: fire beats nature, nature beats water,
+/// and water beats fire.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Element {
+    /// Strong against nature, weak against water.
+    Fire,
+    /// Strong against water, weak against fire.
+    Nature,
+    /// Strong against fire, weak against nature.
+    Water,
+}
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Element::Fire => "Fire",
+            Element::Nature => "Nature",
+            Element::Water => "Water",
+        };
+        f.write_str(name)
+    }
+}
+
+impl Element {
+    /// Returns the element this element is strong against.
+    pub fn beats(self) -> Element {
+        match self {
+            Element::Fire => Element::Nature,
+            Element::Nature => Element::Water,
+            Element::Water => Element::Fire,
+        }
+    }
+}
+
+/// How effective an attack's element is against its target's element.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Effectiveness {
+    /// The attack's element beats the target's.
+    Strong,
+    /// Neither element beats the other, or one of them has no element.
+    Neutral,
+    /// The target's element beats the attack's.
+    Weak,
+}
+
+impl Effectiveness {
+    /// Returns the multiplier applied to damage with this effectiveness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::element::Effectiveness;
+    ///
+    /// assert_eq!(1.5, Effectiveness::Strong.multiplier());
+    /// assert_eq!(1.0, Effectiveness::Neutral.multiplier());
+    /// assert_eq!(0.5, Effectiveness::Weak.multiplier());
+    /// ```
+    pub fn multiplier(self) -> f64 {
+        match self {
+            Effectiveness::Strong => 1.5,
+            Effectiveness::Neutral => 1.0,
+            Effectiveness::Weak => 0.5,
+        }
+    }
+}
+
+/// Looks up how effective an attack of one element is against a target of
+/// another in the affinity chart. Attacks or targets without an element are
+/// always neutral.
+///
+/// # Examples
+///
+/// ```
+/// use druid_game::element::{self, Effectiveness, Element};
+///
+/// assert_eq!(Effectiveness::Strong, element::effectiveness(Some(Element::Fire), Some(Element::Nature)));
+/// assert_eq!(Effectiveness::Weak, element::effectiveness(Some(Element::Fire), Some(Element::Water)));
+/// assert_eq!(Effectiveness::Neutral, element::effectiveness(Some(Element::Fire), Some(Element::Fire)));
+/// assert_eq!(Effectiveness::Neutral, element::effectiveness(None, Some(Element::Water)));
+/// ```
+pub fn effectiveness(attack: Option<Element>, target: Option<Element>) -> Effectiveness {
+    match (attack, target) {
+        (Some(attack), Some(target)) if attack.beats() == target => Effectiveness::Strong,
+        (Some(attack), Some(target)) if target.beats() == attack => Effectiveness::Weak,
+        _ => Effectiveness::Neutral,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_chart_is_a_cycle() {
+        for element in [Element::Fire, Element::Nature, Element::Water] {
+            assert_eq!(element, element.beats().beats().beats(),
+                "Three steps around the chart must return to the same element.");
+            assert_eq!(Effectiveness::Weak, effectiveness(Some(element.beats()), Some(element)),
+                "An element must resist the element it beats.");
+        }
+    }
+}
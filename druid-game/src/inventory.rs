@@ -0,0 +1,237 @@
+//! This module specifies the [`Inventory`] type, as well as the [`Item`]s
+//! that can be stored in it.
+
+use std::fmt::Display;
+
+use crate::weapon::Weapon;
+
+/// The number of weapons and items an [`Inventory`] can hold by default.
+pub const DEFAULT_CAPACITY: usize = 8;
+
+/// What happens when an [`Item`] is used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemEffect {
+    /// Restores the given amount of health.
+    Heal(i32),
+}
+
+/// A representation of a non-weapon item that can be carried.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Item {
+    /// The name used to refer to the item in text.
+    pub name: String,
+    /// What happens when the item is used, if it can be used at all.
+    pub effect: Option<ItemEffect>,
+}
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl Item {
+    /// Constructs an item with the given name and no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::inventory::Item;
+    ///
+    /// Item::new("Pebble".to_string());
+    /// ```
+    pub fn new(name: String) -> Item {
+        Item { name, effect: None }
+    }
+
+    /// Constructs an item with the given name that has an effect when used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::inventory::{Item, ItemEffect};
+    ///
+    /// let herb = Item::with_effect("Herb".to_string(), ItemEffect::Heal(5));
+    /// assert_eq!(Some(ItemEffect::Heal(5)), herb.effect);
+    /// ```
+    pub fn with_effect(name: String, effect: ItemEffect) -> Item {
+        Item { name, effect: Some(effect) }
+    }
+}
+
+/// A collection of weapons and items carried by a combatant, limited by a
+/// total capacity.
+///
+/// The weapon a combatant currently has equipped is not part of their
+/// inventory.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Inventory {
+    weapons: Vec<Weapon>,
+    items: Vec<Item>,
+    capacity: usize,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Inventory::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl Inventory {
+    /// Constructs an empty inventory that can hold the given number of
+    /// weapons and items in total.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::inventory::Inventory;
+    ///
+    /// let inventory = Inventory::new(4);
+    /// assert_eq!(4, inventory.capacity());
+    /// assert!(inventory.is_empty());
+    /// ```
+    pub fn new(capacity: usize) -> Inventory {
+        Inventory {
+            weapons: Vec::new(),
+            items: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Returns the number of weapons and items the inventory can hold in
+    /// total.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of weapons and items in the inventory.
+    pub fn len(&self) -> usize {
+        self.weapons.len() + self.items.len()
+    }
+
+    /// Returns `true` if the inventory holds nothing.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many more weapons and items the inventory can hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::inventory::{Inventory, Item};
+    ///
+    /// let mut inventory = Inventory::new(4);
+    /// inventory.add_item(Item::new("Herb".to_string())).unwrap();
+    /// assert_eq!(3, inventory.remaining_capacity());
+    /// ```
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity.saturating_sub(self.len())
+    }
+
+    /// Returns `true` if the inventory can't hold anything more.
+    pub fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
+    /// Borrows the weapons in the inventory.
+    pub fn weapons(&self) -> &[Weapon] {
+        &self.weapons
+    }
+
+    /// Borrows the items in the inventory.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Adds a weapon to the inventory. If the inventory is full, the weapon
+    /// is handed back as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::inventory::Inventory;
+    /// use druid_game::weapon::Weapon;
+    ///
+    /// let mut inventory = Inventory::new(1);
+    /// assert!(inventory.add_weapon(Weapon::new("Longsword".to_string(), 70, 8)).is_ok());
+    ///
+    /// let rejected = inventory.add_weapon(Weapon::new("Club".to_string(), 60, 6));
+    /// assert_eq!("Club", rejected.unwrap_err().name);
+    /// ```
+    pub fn add_weapon(&mut self, weapon: Weapon) -> Result<(), Weapon> {
+        if self.is_full() {
+            return Err(weapon);
+        }
+        self.weapons.push(weapon);
+        Ok(())
+    }
+
+    /// Removes and returns the weapon at the given index, if there is one.
+    pub fn remove_weapon(&mut self, index: usize) -> Option<Weapon> {
+        if index < self.weapons.len() {
+            Some(self.weapons.remove(index))
+        }
+        else {
+            None
+        }
+    }
+
+    /// Adds an item to the inventory. If the inventory is full, the item is
+    /// handed back as an error.
+    pub fn add_item(&mut self, item: Item) -> Result<(), Item> {
+        if self.is_full() {
+            return Err(item);
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    /// Removes and returns the item at the given index, if there is one.
+    pub fn remove_item(&mut self, index: usize) -> Option<Item> {
+        if index < self.items.len() {
+            Some(self.items.remove(index))
+        }
+        else {
+            None
+        }
+    }
+
+    /// Replaces the weapon at the given index with another, returning the
+    /// one that was stored there.
+    pub(crate) fn replace_weapon(&mut self, index: usize, weapon: Weapon) -> Option<Weapon> {
+        let slot = self.weapons.get_mut(index)?;
+        Some(std::mem::replace(slot, weapon))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_capacity_is_shared() {
+        let mut inventory = Inventory::new(2);
+        inventory.add_weapon(Weapon::new("Longsword".to_string(), 70, 8)).unwrap();
+        inventory.add_item(Item::new("Herb".to_string())).unwrap();
+
+        assert!(inventory.is_full(),
+            "Weapons and items must count towards the same capacity.");
+        assert!(inventory.add_item(Item::new("Herb".to_string())).is_err());
+        assert!(inventory.add_weapon(Weapon::new("Club".to_string(), 60, 6)).is_err());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut inventory = Inventory::default();
+        inventory.add_weapon(Weapon::new("Longsword".to_string(), 70, 8)).unwrap();
+        inventory.add_item(Item::new("Herb".to_string())).unwrap();
+
+        assert_eq!("Longsword", inventory.remove_weapon(0).unwrap().name);
+        assert_eq!(None, inventory.remove_weapon(0));
+        assert_eq!("Herb", inventory.remove_item(0).unwrap().name);
+        assert!(inventory.is_empty());
+    }
+}
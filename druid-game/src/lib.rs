@@ -2,61 +2,51 @@
 
 #![warn(missing_docs)]
 use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+use battle::{Battle, BattleState, CombatantId, Side};
+use combat::Party;
 use combatant::Combatant;
 use weapon::Weapon;
-use battle::{AttackResult, calculate_damage};
-
-use crate::combatant::HealthStatus;
+use service::SeededRng;
 
+pub mod ai;
+pub mod combat;
 pub mod combatant;
+pub mod element;
 pub mod battle;
+pub mod inventory;
+pub mod loot;
+pub mod progression;
+pub mod service;
+pub mod spell;
 pub mod weapon;
+#[cfg(feature = "debug")]
+pub mod debug;
 
 /// The starting point for the game.
 pub fn run() -> Result<(), Box<dyn Error>> {
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64;
+    let mut rng = SeededRng::new(seed);
+
     let mut hero_alice = Combatant::new("Alice".to_string());
     hero_alice.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
     let mut villain_vim = Combatant::new("Vim".to_string());
     villain_vim.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
 
-    attack(&mut hero_alice, &mut villain_vim);
-    println!();
+    let mut battle = Battle::new(Party::new(vec![hero_alice]), Party::new(vec![villain_vim]));
+    let alice = CombatantId { side: Side::Allies, index: 0 };
+    let vim = CombatantId { side: Side::Enemies, index: 0 };
 
-    attack(&mut villain_vim, &mut hero_alice);
-    println!();
-
-    attack(&mut hero_alice, &mut villain_vim);
-    println!();
+    for (attacker, target) in [(alice, vim), (vim, alice), (alice, vim)] {
+        if battle.state() != BattleState::Ongoing {
+            break;
+        }
+        battle.attack(&mut rng, attacker, target)?;
+        for event in battle.take_events() {
+            println!("{}", battle.describe(&event));
+        }
+        println!();
+    }
 
     Ok(())
 }
-
-fn attack(attacker: &mut Combatant, defender: &mut Combatant) {
-    println!("{0} attacks {1}", attacker, defender);
-
-    let dice_roll = 50;
-    let attack_result = battle::resolve_attack(dice_roll, attacker, defender);
-    match attack_result {
-        AttackResult::Miss => println!("{0} missed!", attacker),
-        AttackResult::NoWeapon => println!("{0} didn't equip a weapon!", attacker),
-        AttackResult::DirectHit => {
-            println!("It's a direct hit!");
-            damage_step(&attack_result, attacker, defender);
-        },
-        AttackResult::GlancingBlow => {
-            println!("It's a glancing blow.");
-            damage_step(&attack_result, attacker, defender);
-        },
-    }
-}
-
-fn damage_step(attack_result: &AttackResult, attacker: &mut Combatant, defender: &mut Combatant) {
-    if let Some(damage) = calculate_damage(attack_result, attacker, defender) {
-        println!("{0} takes {1} damage.", defender, damage);
-        let status = defender.health.damage(damage);
-        println!("{0} has {1} hit points remaining.", defender, defender.health.current());
-        if let HealthStatus::Defeated = status {
-            println!("{defender} is defeated!");
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,161 @@
+//! This module specifies the [`DropTable`]s that defeated combatants roll
+//! against, and the [`LootBundle`] that collects what they drop.
+
+use crate::inventory::Item;
+use crate::service::RngService;
+use crate::weapon::Weapon;
+
+/// Something a defeated combatant can drop.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Loot {
+    /// A weapon.
+    Weapon(Weapon),
+    /// A non-weapon item.
+    Item(Item),
+    /// An amount of currency.
+    Gold(i32),
+    /// Nothing at all. Use this to make the other drops less likely.
+    Nothing,
+}
+
+/// A possible drop in a [`DropTable`], along with how likely it is.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DropEntry {
+    /// How likely this drop is, relative to the weights of the other
+    /// entries in the table.
+    pub weight: u32,
+    /// What is dropped.
+    pub loot: Loot,
+}
+
+/// A weighted list of what a combatant may drop when defeated. Each roll
+/// picks exactly one entry, so a table with a single [`Loot::Gold`] entry
+/// always drops gold.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct DropTable {
+    /// The possible drops.
+    pub entries: Vec<DropEntry>,
+}
+
+impl DropTable {
+    /// Constructs a table from `(weight, drop)` pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::loot::{DropTable, Loot};
+    ///
+    /// // Drops 5 gold three times out of four.
+    /// let table = DropTable::new(vec![
+    ///     (3, Loot::Gold(5)),
+    ///     (1, Loot::Nothing),
+    /// ]);
+    /// ```
+    pub fn new(entries: Vec<(u32, Loot)>) -> DropTable {
+        let entries = entries.into_iter()
+            .map(|(weight, loot)| DropEntry { weight, loot })
+            .collect();
+        DropTable { entries }
+    }
+
+    /// Picks one entry from the table at random, weighted by each entry's
+    /// weight. Returns [`Option::None`] if the table is empty or every weight
+    /// is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::loot::{DropTable, Loot};
+    /// use druid_game::service::SeededRng;
+    ///
+    /// let table = DropTable::new(vec![(1, Loot::Gold(5))]);
+    /// let mut rng = SeededRng::new(42);
+    /// assert_eq!(Some(&Loot::Gold(5)), table.roll(&mut rng));
+    ///
+    /// assert_eq!(None, DropTable::default().roll(&mut rng));
+    /// ```
+    pub fn roll(&self, rng: &mut dyn RngService) -> Option<&Loot> {
+        let total: u32 = self.entries.iter().map(|entry| entry.weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.range(1, total as i32) as u32;
+        for entry in &self.entries {
+            if roll <= entry.weight {
+                return Some(&entry.loot);
+            }
+            roll -= entry.weight;
+        }
+        unreachable!("Roll must land within the total weight")
+    }
+}
+
+/// Everything dropped by the defeated side of a battle.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LootBundle {
+    /// The weapons dropped.
+    pub weapons: Vec<Weapon>,
+    /// The items dropped.
+    pub items: Vec<Item>,
+    /// The total currency dropped.
+    pub gold: i32,
+}
+
+impl LootBundle {
+    /// Adds a drop to the bundle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::loot::{Loot, LootBundle};
+    ///
+    /// let mut loot = LootBundle::default();
+    /// loot.add(Loot::Gold(5));
+    /// loot.add(Loot::Gold(3));
+    /// loot.add(Loot::Nothing);
+    /// assert_eq!(8, loot.gold);
+    /// assert!(loot.weapons.is_empty());
+    /// ```
+    pub fn add(&mut self, loot: Loot) {
+        match loot {
+            Loot::Weapon(weapon) => self.weapons.push(weapon),
+            Loot::Item(item) => self.items.push(item),
+            Loot::Gold(gold) => self.gold += gold,
+            Loot::Nothing => {},
+        }
+    }
+
+    /// Returns `true` if nothing was dropped.
+    pub fn is_empty(&self) -> bool {
+        self.weapons.is_empty() && self.items.is_empty() && self.gold == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::service::SeededRng;
+
+    #[test]
+    fn test_roll_respects_weights() {
+        let table = DropTable::new(vec![
+            (3, Loot::Gold(1)),
+            (1, Loot::Nothing),
+            (0, Loot::Gold(100)),
+        ]);
+        let mut rng = SeededRng::new(9);
+
+        let rolls: Vec<&Loot> = (0..1000).map(|_| table.roll(&mut rng).unwrap()).collect();
+        let gold = rolls.iter().filter(|&&loot| *loot == Loot::Gold(1)).count();
+        assert!((650..850).contains(&gold),
+            "Drops must be picked in proportion to their weight, got {gold} of 1000.");
+        assert!(!rolls.contains(&&Loot::Gold(100)),
+            "Entries with no weight must never be picked.");
+    }
+}
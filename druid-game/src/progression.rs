@@ -0,0 +1,149 @@
+//! This module specifies how combatants grow stronger with experience: the
+//! [`XpTable`] that decides when they level up, and the [`GrowthCurve`] that
+//! decides what they gain when they do.
+
+use crate::combatant::CombatStats;
+
+/// A table of how much total experience is needed to reach each level.
+///
+/// Every combatant starts at level 1 with no experience.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XpTable {
+    thresholds: Vec<u32>,
+}
+
+impl Default for XpTable {
+    /// Constructs a quadratic table with a step of 10, up to level 20.
+    fn default() -> Self {
+        XpTable::quadratic(10, 20)
+    }
+}
+
+impl XpTable {
+    /// Constructs a table from a list of experience thresholds. The first
+    /// threshold is the total experience needed to reach level 2, the second
+    /// for level 3, and so on. The last threshold decides the maximum level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thresholds are not in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::progression::XpTable;
+    ///
+    /// let table = XpTable::new(vec![10, 25, 50]);
+    /// assert_eq!(1, table.level_for(9));
+    /// assert_eq!(2, table.level_for(10));
+    /// assert_eq!(4, table.level_for(1000));
+    /// assert_eq!(4, table.max_level());
+    /// ```
+    pub fn new(thresholds: Vec<u32>) -> XpTable {
+        assert!(thresholds.windows(2).all(|pair| pair[0] <= pair[1]),
+            "Experience thresholds must be in ascending order");
+        XpTable { thresholds }
+    }
+
+    /// Constructs a table where reaching level `n` takes `step * (n - 1)²`
+    /// total experience, up to the given maximum level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::progression::XpTable;
+    ///
+    /// let table = XpTable::quadratic(10, 5);
+    /// assert_eq!(2, table.level_for(10));
+    /// assert_eq!(3, table.level_for(40));
+    /// assert_eq!(5, table.max_level());
+    /// ```
+    pub fn quadratic(step: u32, max_level: u32) -> XpTable {
+        let thresholds = (2..=max_level)
+            .map(|level| step * (level - 1) * (level - 1))
+            .collect();
+        XpTable::new(thresholds)
+    }
+
+    /// Returns the level reached with the given total experience.
+    pub fn level_for(&self, experience: u32) -> u32 {
+        let thresholds_met = self.thresholds.iter()
+            .take_while(|&&threshold| experience >= threshold)
+            .count();
+        1 + thresholds_met as u32
+    }
+
+    /// Returns the highest level in the table.
+    pub fn max_level(&self) -> u32 {
+        1 + self.thresholds.len() as u32
+    }
+
+    /// Returns how much more experience is needed to reach the next level,
+    /// or [`Option::None`] if the maximum level has been reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::progression::XpTable;
+    ///
+    /// let table = XpTable::new(vec![10, 25]);
+    /// assert_eq!(Some(4), table.xp_to_next_level(6));
+    /// assert_eq!(None, table.xp_to_next_level(25));
+    /// ```
+    pub fn xp_to_next_level(&self, experience: u32) -> Option<u32> {
+        self.thresholds.iter()
+            .find(|&&threshold| experience < threshold)
+            .map(|threshold| threshold - experience)
+    }
+}
+
+/// What a combatant gains each time they level up.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrowthCurve {
+    /// Added to the combatant's stats each level.
+    pub stats: CombatStats,
+    /// Added to the combatant's maximum health each level.
+    pub health: i32,
+}
+
+impl Default for GrowthCurve {
+    /// Constructs a growth curve that raises every stat by 1 and maximum
+    /// health by 2 each level.
+    fn default() -> Self {
+        GrowthCurve {
+            stats: CombatStats {
+                accuracy: 1,
+                evasion: 1,
+                strength: 1,
+                defense: 1,
+                speed: 1,
+            },
+            health: 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_level_thresholds() {
+        let table = XpTable::new(vec![10, 30]);
+
+        assert_eq!(1, table.level_for(0));
+        assert_eq!(2, table.level_for(10),
+            "Meeting a threshold exactly must reach the level.");
+        assert_eq!(2, table.level_for(29));
+        assert_eq!(3, table.level_for(30));
+        assert_eq!(3, table.level_for(u32::MAX),
+            "Level must not exceed the maximum.");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unordered_thresholds() {
+        XpTable::new(vec![30, 10]);
+    }
+}
@@ -0,0 +1,116 @@
+//! This module specifies services that the game relies on, but which each
+//! frontend may want to provide in its own way, such as the [`RngService`].
+
+/// A source of random numbers for dice rolls.
+///
+/// Frontends can supply their own implementation, backed by whatever source
+/// of randomness their platform provides. [`SeededRng`] is a deterministic
+/// implementation, useful for tests and reproducible battles.
+pub trait RngService {
+    /// Returns a random integer between `min` and `max` inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Implementations may panic if `min` is greater than `max`.
+    fn range(&mut self, min: i32, max: i32) -> i32;
+
+    /// Rolls a hundred-sided die, returning a number between 1 and 100
+    /// inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::service::{RngService, SeededRng};
+    ///
+    /// let mut rng = SeededRng::new(42);
+    /// let roll = rng.roll_d100();
+    /// assert!((1..=100).contains(&roll));
+    /// ```
+    fn roll_d100(&mut self) -> i32 {
+        self.range(1, 100)
+    }
+}
+
+/// A deterministic [`RngService`] that produces the same sequence of numbers
+/// for the same seed.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Constructs a random number generator from the given seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::service::{RngService, SeededRng};
+    ///
+    /// let mut first = SeededRng::new(7);
+    /// let mut second = SeededRng::new(7);
+    /// assert_eq!(first.roll_d100(), second.roll_d100());
+    /// ```
+    pub fn new(seed: u64) -> SeededRng {
+        SeededRng { state: seed }
+    }
+
+    /// Returns the next number in the sequence. This uses the SplitMix64
+    /// algorithm.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl RngService for SeededRng {
+    /// Returns a random integer between `min` and `max` inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    fn range(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min <= max, "Range minimum {min} must not exceed maximum {max}");
+        let span = (max as i64 - min as i64 + 1) as u64;
+        let offset = self.next_u64() % span;
+        (min as i64 + offset as i64) as i32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut first = SeededRng::new(1234);
+        let mut second = SeededRng::new(1234);
+
+        for _ in 0..100 {
+            assert_eq!(first.range(0, 1000), second.range(0, 1000),
+                "Generators with the same seed must produce the same rolls.");
+        }
+    }
+
+    #[test]
+    fn test_range_is_inclusive() {
+        let mut rng = SeededRng::new(99);
+        let rolls: Vec<i32> = (0..1000).map(|_| rng.range(-2, 2)).collect();
+
+        assert!(rolls.iter().all(|roll| (-2..=2).contains(roll)),
+            "Rolls must stay within the given range.");
+        assert!(rolls.contains(&-2) && rolls.contains(&2),
+            "Both ends of the range must be reachable.");
+    }
+
+    #[test]
+    fn test_range_extremes() {
+        let mut rng = SeededRng::new(5);
+
+        assert_eq!(3, rng.range(3, 3));
+        let roll = rng.range(i32::MIN, i32::MAX);
+        assert!((i32::MIN..=i32::MAX).contains(&roll));
+    }
+}
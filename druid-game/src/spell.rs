@@ -0,0 +1,86 @@
+//! This module specifies the [`Spell`] type, used by combatants to spend
+//! mana on magical attacks and healing.
+
+use std::fmt::Display;
+
+use crate::element::Element;
+
+/// What a [`Spell`] does to its target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpellEffect {
+    /// Deals damage to an opponent. The spell must roll to hit, like an
+    /// attack, but ignores the target's defense.
+    Damage,
+    /// Restores health to the caster or an ally. Healing spells never miss.
+    Heal,
+}
+
+/// A representation of a spell that can be cast in combat.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spell {
+    /// The name used to refer to the spell in text.
+    pub name: String,
+    /// How much mana the caster spends to cast the spell.
+    pub mana_cost: i32,
+    /// The base frequency with which this spell hits, compared to a roll
+    /// from 1 through 100. Ignored by healing spells.
+    pub hit_rate: i32,
+    /// The damage dealt on a direct hit, or the health restored.
+    pub power: i32,
+    /// What the spell does to its target.
+    pub effect: SpellEffect,
+    /// The element of a damaging spell, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub element: Option<Element>,
+}
+impl Display for Spell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl Spell {
+    /// Constructs a spell with no element that damages an opponent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::spell::{Spell, SpellEffect};
+    ///
+    /// let bolt = Spell::damage("Bolt".to_string(), 3, 80, 6);
+    /// assert_eq!(SpellEffect::Damage, bolt.effect);
+    /// ```
+    pub fn damage(name: String, mana_cost: i32, hit_rate: i32, power: i32) -> Spell {
+        Spell {
+            name,
+            mana_cost,
+            hit_rate,
+            power,
+            effect: SpellEffect::Damage,
+            element: None,
+        }
+    }
+
+    /// Constructs a spell that restores health to the caster or an ally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid_game::spell::{Spell, SpellEffect};
+    ///
+    /// let mend = Spell::heal("Mend".to_string(), 2, 5);
+    /// assert_eq!(SpellEffect::Heal, mend.effect);
+    /// ```
+    pub fn heal(name: String, mana_cost: i32, power: i32) -> Spell {
+        Spell {
+            name,
+            mana_cost,
+            hit_rate: 100,
+            power,
+            effect: SpellEffect::Heal,
+            element: None,
+        }
+    }
+}
@@ -1,8 +1,15 @@
-//! This module specifies the [`Weapon`] type.
+//! This module specifies the [`Weapon`] type, as well as the 
+//! [`WeaponDatabase`] used to look weapons up by name.
 
 use std::fmt::Display;
 
+use crate::combatant::CombatStats;
+use crate::element::Element;
+use crate::service::RngService;
+
 /// A representation of a weapon used in combat.  
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Weapon {
     /// The name used to refer to the weapon in text.
     pub name: String,
@@ -11,6 +18,25 @@ pub struct Weapon {
     pub hit_rate: i32,
     /// The base amount of damage this weapon deals on a direct hit.
     pub damage: i32,
+    /// How far the damage of each attack may stray from the base damage in 
+    /// either direction. A weapon with `10` damage and a variance of `2` 
+    /// deals from 8 through 12 damage on a direct hit. 
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub damage_variance: i32,
+    /// Stats added to the wielder's own while this weapon is equipped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stat_bonus: CombatStats,
+    /// How far the weapon can strike without penalty. Combatants in each 
+    /// other's front rows are 1 apart, and each back row adds 1 more. 
+    #[cfg_attr(feature = "serde", serde(default = "default_reach"))]
+    pub reach: u32,
+    /// The element of the weapon's attacks, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub element: Option<Element>,
+    /// Whether the weapon needs both hands, leaving nothing to hold in the 
+    /// wielder's [`OffHand`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub two_handed: bool,
 }
 impl Display for Weapon {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -19,7 +45,9 @@ impl Display for Weapon {
 }
 
 impl Weapon {
-    /// Constructs a weapon with the given parameters.
+    /// Constructs a one-handed melee weapon with the given parameters, 
+    /// with no damage variance, granting no stat bonuses and with no 
+    /// element.
     /// 
     /// # Examples
     /// 
@@ -31,6 +59,158 @@ impl Weapon {
     /// Weapon::new("Blessed Longsword".to_string(), 90, 12);
     /// ``` 
     pub fn new(name: String, hit_rate: i32, damage: i32) -> Weapon {
-        Weapon { name, hit_rate, damage }
+        Weapon { 
+            name, 
+            hit_rate, 
+            damage, 
+            damage_variance: 0,
+            stat_bonus: CombatStats::new(),
+            reach: 1,
+            element: None,
+            two_handed: false,
+        }
+    }
+
+    /// Rolls the weapon's damage for one attack, anywhere within its 
+    /// [`damage_variance`](Weapon::damage_variance) of the base damage. 
+    /// Weapons with no variance always return their base damage without 
+    /// rolling.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::service::SeededRng;
+    /// use druid_game::weapon::Weapon;
+    /// 
+    /// let mut rng = SeededRng::new(42);
+    /// let mut axe = Weapon::new("Axe".to_string(), 60, 10);
+    /// assert_eq!(10, axe.roll_damage(&mut rng));
+    /// 
+    /// axe.damage_variance = 2;
+    /// assert!((8..=12).contains(&axe.roll_damage(&mut rng)));
+    /// ```
+    pub fn roll_damage(&self, rng: &mut dyn RngService) -> i32 {
+        if self.damage_variance == 0 {
+            return self.damage;
+        }
+        let variance = self.damage_variance.abs();
+        rng.range(self.damage - variance, self.damage + variance)
+    }
+}
+
+/// The reach of weapons loaded from data that don't give one: melee range.
+#[cfg(feature = "serde")]
+fn default_reach() -> u32 {
+    1
+}
+
+/// A representation of a shield, held in the off hand to protect the 
+/// wielder.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shield {
+    /// The name used to refer to the shield in text.
+    pub name: String,
+    /// Stats added to the wielder's own while this shield is equipped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stat_bonus: CombatStats,
+}
+impl Display for Shield {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl Shield {
+    /// Constructs a shield that raises the wielder's defense by the given 
+    /// amount.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::weapon::Shield;
+    /// 
+    /// let buckler = Shield::new("Buckler".to_string(), 2);
+    /// assert_eq!(2, buckler.stat_bonus.defense);
+    /// ```
+    pub fn new(name: String, defense: i32) -> Shield {
+        Shield { 
+            name, 
+            stat_bonus: CombatStats { defense, ..CombatStats::new() },
+        }
+    }
+}
+
+/// What a combatant holds in their off hand, beside a one-handed weapon.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OffHand {
+    /// A shield, adding its stat bonus to the wielder's stats.
+    Shield(Shield),
+    /// A second one-handed weapon, granting an extra attack at a penalty. 
+    /// See [`OFF_HAND_PENALTY`](crate::battle::OFF_HAND_PENALTY).
+    Weapon(Weapon),
+}
+impl Display for OffHand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OffHand::Shield(shield) => shield.fmt(f),
+            OffHand::Weapon(weapon) => weapon.fmt(f),
+        }
     }
-}
\ No newline at end of file
+}
+
+/// A collection of weapon definitions, looked up by name. This lets weapons 
+/// be defined in data files instead of in code.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct WeaponDatabase {
+    weapons: Vec<Weapon>,
+}
+
+impl WeaponDatabase {
+    /// Constructs a database of the given weapons.
+    pub fn new(weapons: Vec<Weapon>) -> WeaponDatabase {
+        WeaponDatabase { weapons }
+    }
+
+    /// Loads a database from a JSON array of weapons. Stat bonuses that are 
+    /// left out default to `0`.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the text isn't valid JSON, or doesn't describe a 
+    /// list of weapons.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use druid_game::weapon::WeaponDatabase;
+    /// 
+    /// let json = r#"[{
+    ///     "name": "Longsword",
+    ///     "hit_rate": 70,
+    ///     "damage": 8,
+    ///     "stat_bonus": { "strength": 1 }
+    /// }]"#;
+    /// let weapons = WeaponDatabase::from_json(json).unwrap();
+    /// assert_eq!(8, weapons.get("longsword").unwrap().damage);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<WeaponDatabase, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Borrows the weapon with the given name, matched without regard to 
+    /// case.
+    pub fn get(&self, name: &str) -> Option<&Weapon> {
+        self.weapons.iter()
+            .find(|weapon| weapon.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Borrows every weapon in the database.
+    pub fn weapons(&self) -> &[Weapon] {
+        &self.weapons
+    }
+}
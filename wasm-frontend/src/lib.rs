@@ -3,14 +3,28 @@ mod utils;
 use web_sys::console;
 use wasm_bindgen::prelude::*;
 
+use druid_game::battle::{Battle, BattleState, CombatantId, Side};
+use druid_game::combat::Party;
 use druid_game::combatant::Combatant;
+use druid_game::service::RngService;
 use druid_game::weapon::Weapon;
-use druid_game::battle;
-use druid_game::battle::AttackResult;
 
 #[wasm_bindgen]
 extern {
     fn alert(s: &str);
+
+    #[wasm_bindgen(js_namespace = Math)]
+    fn random() -> f64;
+}
+
+/// Rolls dice using the browser's `Math.random`.
+struct BrowserRng;
+
+impl RngService for BrowserRng {
+    fn range(&mut self, min: i32, max: i32) -> i32 {
+        let span = (max as i64 - min as i64 + 1) as f64;
+        (min as i64 + (random() * span).floor() as i64) as i32
+    }
 }
 
 // A macro to provide `println!(..)`-style syntax for `console.log` logging.
@@ -22,48 +36,27 @@ macro_rules! log {
 
 #[wasm_bindgen]
 pub fn run() {
+    let mut rng = BrowserRng;
+
     let mut hero_alice = Combatant::new("Alice".to_string());
     hero_alice.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
     let mut villain_vim = Combatant::new("Vim".to_string());
     villain_vim.give_weapon(Weapon::new("Longsword".to_string(), 70, 8));
 
-    attack(&mut hero_alice, &mut villain_vim);
-    console::log_0();
-
-    attack(&mut villain_vim, &mut hero_alice);
-    console::log_0();
-
-    attack(&mut hero_alice, &mut villain_vim);
-    console::log_0();
-}
-
-fn attack(attacker: &mut Combatant, defender: &mut Combatant) {
-    log!("{0} attacks {1}", attacker, defender);
-    let dice_roll = 50;
-    let attack_result = battle::resolve_attack(dice_roll, attacker, defender);
-    match attack_result {
-        AttackResult::Miss => log!("{0} missed!", attacker),
-        AttackResult::NoWeapon => log!("{0} didn't equip a weapon!", attacker),
-        AttackResult::DirectHit => {
-            log!("It's a direct hit!");
-            damage_step(&attack_result, attacker, defender);
-        },
-        AttackResult::GlancingBlow => {
-            log!("It's a glancing blow.");
-            damage_step(&attack_result, attacker, defender);
-        },
-    }
-}
+    let mut battle = Battle::new(Party::new(vec![hero_alice]), Party::new(vec![villain_vim]));
+    let alice = CombatantId { side: Side::Allies, index: 0 };
+    let vim = CombatantId { side: Side::Enemies, index: 0 };
 
-fn damage_step(attack_result: &AttackResult, attacker: &mut Combatant, defender: &mut Combatant) {
-    use druid_game::combatant::HealthStatus;
-
-    if let Some(damage) = battle::calculate_damage(attack_result, attacker, defender) {
-        log!("{0} takes {1} damage.", defender, damage);
-        let status = defender.health.damage(damage);
-        log!("{0} has {1} hit points remaining.", defender, defender.health.current());
-        if let HealthStatus::Defeated = status {
-            log!("{defender} is defeated!");
+    for (attacker, target) in [(alice, vim), (vim, alice), (alice, vim)] {
+        if battle.state() != BattleState::Ongoing {
+            break;
+        }
+        if let Err(e) = battle.attack(&mut rng, attacker, target) {
+            log!("{e}");
         }
+        for event in battle.take_events() {
+            log!("{}", battle.describe(&event));
+        }
+        console::log_0();
     }
-}
\ No newline at end of file
+}